@@ -2,7 +2,7 @@ import { chapterLogics } from '$lib/logic';
 import { Logic } from '$rust';
 import TOML from 'smol-toml';
 import * as v from 'valibot';
-import { symbolAsciiStr, symbolChar, symbolIter } from '$rust';
+import { symbolAsciiStr, symbolIter, symbolUnicodeStr } from '$rust';
 
 export const singleExampleSchema = v.object({
 	premises: v.optional(v.string()),
@@ -43,6 +43,10 @@ export const normalModalKind = v.object({
 	extendable: v.optional(v.boolean())
 });
 
+export const conditionalKind = v.object({
+	plus: v.optional(v.boolean())
+});
+
 export function logicOfChapter(chapter: number, example: Example) {
 	if (chapter === 1) {
 		return Logic.classical();
@@ -56,6 +60,13 @@ export function logicOfChapter(chapter: number, example: Example) {
 			transitive ?? false,
 			extendable ?? false
 		);
+	} else if (chapter === 5) {
+		const { plus } = v.parse(v.optional(conditionalKind, {}), example.kind);
+		return Logic.conditional(plus ?? false);
+	} else if (chapter === 6) {
+		return Logic.intuitionist();
+	} else if (chapter === 7) {
+		return Logic.manyValued(v.parse(v.string(), example.kind));
 	} else {
 		throw new Error(`Invalid chapter: ${chapter}`);
 	}
@@ -67,7 +78,8 @@ export function validateChapter(chapter: string): number | null {
 		return null;
 	}
 
-	if (output < 1 || output > Object.keys(chapterLogics).length) {
+	// Chapters without examples, such as 4, have no logic.
+	if (!(output in chapterLogics)) {
 		return null;
 	}
 
@@ -76,8 +88,8 @@ export function validateChapter(chapter: string): number | null {
 
 export function replaceAsciiWithUnicode(text: string) {
 	for (const symbol of symbolIter()) {
-		console.log('replacing ', symbolAsciiStr(symbol), ' with ', symbolChar(symbol));
-		text = text.replaceAll(symbolAsciiStr(symbol), symbolChar(symbol));
+		console.log('replacing ', symbolAsciiStr(symbol), ' with ', symbolUnicodeStr(symbol));
+		text = text.replaceAll(symbolAsciiStr(symbol), symbolUnicodeStr(symbol));
 	}
 
 	return text;
@@ -1,6 +1,13 @@
-import { Classical, Modal, NormalModal } from '$rust';
+import { Classical, Conditional, Intuitionist, ManyValued, Modal, NormalModal } from '$rust';
 
-export const logics = ['classical', 'modal', 'normalModal'] as const;
+export const logics = [
+	'classical',
+	'modal',
+	'normalModal',
+	'conditional',
+	'intuitionist',
+	'manyValued'
+] as const;
 export type LogicKind = (typeof logics)[number];
 export const asLogicKind = (value: string): LogicKind => {
 	if (logics.includes(value as LogicKind)) {
@@ -13,17 +20,26 @@ export const asLogicKind = (value: string): LogicKind => {
 export const classObject = {
 	classical: Classical,
 	modal: Modal,
-	normalModal: NormalModal
+	normalModal: NormalModal,
+	conditional: Conditional,
+	intuitionist: Intuitionist,
+	manyValued: ManyValued
 } satisfies Record<LogicKind, unknown>;
 
 export const displayName = {
 	classical: 'Classical',
 	modal: 'Basic modal',
-	normalModal: 'Normal modal'
+	normalModal: 'Normal modal',
+	conditional: 'Conditional',
+	intuitionist: 'Intuitionist',
+	manyValued: 'Many-valued'
 } satisfies Record<LogicKind, string>;
 
 export const chapterLogics: Record<string, LogicKind> = {
 	1: 'classical',
 	2: 'modal',
-	3: 'normalModal'
+	3: 'normalModal',
+	5: 'conditional',
+	6: 'intuitionist',
+	7: 'manyValued'
 };
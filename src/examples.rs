@@ -0,0 +1,348 @@
+//! Example arguments with their expected outcome, read from TOML files, so
+//! that they can be used as a regression suite for the prover.
+//!
+//! Two schemas are supported:
+//!
+//! - The one of `examples-graham-priest.toml`, with keys such as
+//!   `[1.exercise.1.a]` whose chapter decides the logic, each with `premises`
+//!   (comma separated, optional), `conclusion`, `holds` (defaults to `true`)
+//!   and, for chapter 3, `kind = { reflexive = true, ... }`. Chapter 5 is
+//!   the conditional logic C, or C+ with `kind = { plus = true }`, chapter 6
+//!   is intuitionist logic and chapter 7 needs the many-valued logic, such as
+//!   `kind = "LP"`.
+//! - The one of `examples.toml`, with an `[[example]]` array whose
+//!   `statements` are either strings (using the `type` and `result` of the
+//!   example) or tables with their own `type`, `statement` and `result`.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use toml::{Table, Value};
+
+use crate::{
+    InferenceStatus, Limits, PartialTableau, TableauParseError,
+    logic::{DynLogic, Logic, ParseError, conditional::Conditional, normal_modal::NormalModal},
+};
+
+/// An argument along with the logic it should be checked in and whether it
+/// should hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// Where the example comes from, such as `1.exercise.1.a`.
+    pub name: String,
+    pub logic: DynLogic,
+    /// The argument, in the form `Σ ⊢ A`.
+    pub argument: String,
+    pub holds: bool,
+}
+
+/// The result of checking an [`Example`].
+#[derive(Debug, Clone)]
+pub struct Verification {
+    /// How inference ended, or why the argument couldn't be parsed.
+    pub status: Result<InferenceStatus, TableauParseError<ParseError>>,
+    /// Time spent parsing and inferring.
+    pub elapsed: Duration,
+}
+
+/// Parses a file in either of the supported schemas (see the [module
+/// docs](self)).
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::examples;
+///
+/// let examples = examples::parse(r#"
+///     [3.3.3]
+///     conclusion = "[]p > p"
+///     kind = { reflexive = true }
+/// "#).unwrap();
+///
+/// assert_eq!(examples[0].name, "3.3.3");
+/// assert_eq!(examples[0].argument, " ⊢ []p > p");
+/// assert!(examples[0].verify(&Default::default()).passed(&examples[0]));
+/// ```
+pub fn parse(source: &str) -> Result<Vec<Example>, ExamplesError> {
+    let table = source.parse::<Table>().map_err(ExamplesError::Toml)?;
+    if table.contains_key("example") {
+        parse_list(&table)
+    } else {
+        parse_chapters(&table)
+    }
+}
+
+/// Parses the schema of `examples-graham-priest.toml`.
+fn parse_chapters(table: &Table) -> Result<Vec<Example>, ExamplesError> {
+    let mut output = Vec::new();
+    for (chapter, value) in table {
+        collect_chapter(chapter, chapter, value, &mut output)?;
+    }
+
+    Ok(output)
+}
+
+/// Adds every example under `value` to `output`, where `name` is the dotted
+/// key that leads to `value`.
+fn collect_chapter(
+    chapter: &str,
+    name: &str,
+    value: &Value,
+    output: &mut Vec<Example>,
+) -> Result<(), ExamplesError> {
+    let Some(table) = value.as_table() else {
+        return Err(ExamplesError::invalid(name, "expected a table"));
+    };
+
+    if !table.contains_key("conclusion") {
+        for (key, value) in table {
+            collect_chapter(chapter, &format!("{name}.{key}"), value, output)?;
+        }
+
+        return Ok(());
+    }
+
+    let premises = optional_str(table, name, "premises")?.unwrap_or("");
+    let conclusion = optional_str(table, name, "conclusion")?.unwrap_or("");
+    let holds = match table.get("holds") {
+        None => true,
+        Some(value) => value
+            .as_bool()
+            .ok_or_else(|| ExamplesError::invalid(name, "`holds` should be a boolean"))?,
+    };
+
+    output.push(Example {
+        name: name.to_string(),
+        logic: chapter_logic(chapter, name, table.get("kind"))?,
+        argument: format!("{premises} ⊢ {conclusion}"),
+        holds,
+    });
+
+    Ok(())
+}
+
+/// The logic used in the given chapter of Priest's book.
+fn chapter_logic(
+    chapter: &str,
+    name: &str,
+    kind: Option<&Value>,
+) -> Result<DynLogic, ExamplesError> {
+    Ok(match chapter {
+        "1" => DynLogic::Classical(Default::default()),
+        "2" => DynLogic::Modal(Default::default()),
+        "3" => {
+            let Some(kind) = kind.and_then(Value::as_table) else {
+                return Err(ExamplesError::invalid(name, "missing `kind` table"));
+            };
+
+            let mut logic = NormalModal::new();
+            for (condition, value) in kind {
+                if value.as_bool() != Some(true) {
+                    continue;
+                }
+
+                logic = match condition.as_str() {
+                    "reflexive" => logic.reflexive(),
+                    "symmetric" => logic.symmetric(),
+                    "transitive" => logic.transitive(),
+                    "extendable" => logic.extendable(),
+                    condition => {
+                        return Err(ExamplesError::invalid(
+                            name,
+                            format!("unknown frame condition `{condition}`"),
+                        ));
+                    }
+                };
+            }
+
+            DynLogic::NormalModal(logic)
+        }
+        "5" => {
+            let plus = match kind.and_then(|kind| kind.get("plus")) {
+                None => false,
+                Some(plus) => plus.as_bool().ok_or_else(|| {
+                    ExamplesError::invalid(name, "`kind.plus` should be a boolean")
+                })?,
+            };
+
+            DynLogic::Conditional(Conditional { plus })
+        }
+        "6" => DynLogic::Intuitionist(Default::default()),
+        "7" => {
+            let Some(kind) = kind.and_then(Value::as_str) else {
+                return Err(ExamplesError::invalid(name, "missing `kind` string"));
+            };
+
+            match kind.parse() {
+                Ok(logic @ DynLogic::ManyValued(_)) => logic,
+                _ => {
+                    return Err(ExamplesError::invalid(
+                        name,
+                        format!("unknown many-valued logic `{kind}`"),
+                    ));
+                }
+            }
+        }
+        chapter => {
+            return Err(ExamplesError::invalid(
+                name,
+                format!("no logic for chapter {chapter}"),
+            ));
+        }
+    })
+}
+
+/// Parses the schema of `examples.toml`.
+fn parse_list(table: &Table) -> Result<Vec<Example>, ExamplesError> {
+    let Some(examples) = table.get("example").and_then(Value::as_array) else {
+        return Err(ExamplesError::invalid(
+            "example",
+            "expected an array of tables",
+        ));
+    };
+
+    let mut output = Vec::new();
+    for (i, example) in examples.iter().enumerate() {
+        let fallback_name = format!("example {}", i + 1);
+        let Some(example) = example.as_table() else {
+            return Err(ExamplesError::invalid(&fallback_name, "expected a table"));
+        };
+
+        let name = optional_str(example, &fallback_name, "name")?.unwrap_or(&fallback_name);
+        let Some(statements) = example.get("statements").and_then(Value::as_array) else {
+            return Err(ExamplesError::invalid(
+                name,
+                "`statements` should be an array",
+            ));
+        };
+
+        for statement in statements {
+            let (kind, argument, result) = match statement {
+                Value::String(argument) => (
+                    optional_str(example, name, "type")?,
+                    argument.as_str(),
+                    optional_str(example, name, "result")?,
+                ),
+                Value::Table(statement) => (
+                    optional_str(statement, name, "type")?,
+                    optional_str(statement, name, "statement")?
+                        .ok_or_else(|| ExamplesError::invalid(name, "missing `statement`"))?,
+                    optional_str(statement, name, "result")?,
+                ),
+                _ => return Err(ExamplesError::invalid(name, "invalid statement")),
+            };
+
+            let kind = kind.ok_or_else(|| ExamplesError::invalid(name, "missing `type`"))?;
+            let logic_name = match kind {
+                "modal" => "K".to_string(),
+                kind => match kind.strip_prefix("modal-") {
+                    Some(conditions) => format!("K{conditions}"),
+                    None => kind.to_string(),
+                },
+            };
+
+            output.push(Example {
+                name: name.to_string(),
+                logic: logic_name
+                    .parse()
+                    .map_err(|err| ExamplesError::invalid(name, format!("{err}")))?,
+                argument: argument.to_string(),
+                holds: match result {
+                    Some("holds") => true,
+                    Some("not-holds") => false,
+                    _ => {
+                        return Err(ExamplesError::invalid(
+                            name,
+                            "`result` should be \"holds\" or \"not-holds\"",
+                        ));
+                    }
+                },
+            });
+        }
+    }
+
+    Ok(output)
+}
+
+fn optional_str<'a>(
+    table: &'a Table,
+    name: &str,
+    key: &str,
+) -> Result<Option<&'a str>, ExamplesError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => value
+            .as_str()
+            .map(Some)
+            .ok_or_else(|| ExamplesError::invalid(name, format!("`{key}` should be a string"))),
+    }
+}
+
+impl Example {
+    /// Builds and infers the tableau of the example, stopping at the given
+    /// limits.
+    pub fn verify(&self, limits: &Limits) -> Verification {
+        let start = Instant::now();
+        let status = PartialTableau::parse_with(self.logic.clone(), &self.argument, |expr| {
+            self.logic.parse_expr(expr)
+        })
+        .map(|mut tableau| tableau.infer_with_limits(limits));
+
+        Verification {
+            status,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+impl Verification {
+    /// Whether the tableau finished with the outcome the example expects.
+    pub fn passed(&self, example: &Example) -> bool {
+        matches!(
+            (&self.status, example.holds),
+            (Ok(InferenceStatus::Closed), true) | (Ok(InferenceStatus::Open), false)
+        )
+    }
+}
+
+/// Error returned when an examples file can't be read.
+#[derive(Debug, Clone)]
+pub enum ExamplesError {
+    /// The file isn't valid TOML.
+    Toml(toml::de::Error),
+    /// The example at `name` doesn't follow the schema.
+    Invalid { name: String, reason: String },
+}
+
+impl ExamplesError {
+    fn invalid(name: &str, reason: impl Into<String>) -> Self {
+        Self::Invalid {
+            name: name.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExamplesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Toml(err) => err.fmt(f),
+            Self::Invalid { name, reason } => write!(f, "invalid example `{name}`: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ExamplesError {}
+
+impl fmt::Display for Example {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logic = match &self.logic {
+            DynLogic::Classical(_) => "classical".into(),
+            logic => logic.symbol(),
+        };
+
+        write!(f, "{} ({logic}): {}", self.name, self.argument)
+    }
+}
@@ -4,7 +4,15 @@ pub mod logic;
 use logic::Logic;
 
 mod tableau;
-pub use tableau::PartialTableau;
+#[cfg(feature = "parse")]
+pub use tableau::{ArgumentPart, TableauParseError};
+pub use tableau::{
+    DeathReason, InferenceStatus, Justification, LatexPackage, Limit, Limits, NodeId,
+    PartialTableau, Renderer, Tableau,
+};
+
+#[cfg(feature = "examples")]
+pub mod examples;
 
 // pub mod classical;
 // pub mod modal;
@@ -1,17 +1,32 @@
+#[cfg(feature = "parse")]
+use crate::logic::ParseError;
 use crate::{
     PartialTableau,
-    logic::{Logic, classical, modal, normal_modal},
-    tableau::Branch,
+    logic::{
+        Logic, Print, Printer, classical, conditional, intuitionist, many_valued, modal,
+        non_normal, normal_modal, tense,
+    },
+    tableau::{Branch, NodeId},
 };
 
-use super::InferenceRule;
+use super::{InferenceRule, Place};
 
-use std::{borrow::Cow, fmt};
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 make_dyn_logic![
     Classical, classical::Classical, "Classical";
     Modal, modal::Modal, "Modal";
     NormalModal, normal_modal::NormalModal, "Normal Modal";
+    NonNormal, non_normal::NonNormal, "Non-normal Modal";
+    Tense, tense::Tense, "Tense";
+    Intuitionist, intuitionist::Intuitionist, "Intuitionist";
+    Conditional, conditional::Conditional, "Conditional";
+    ManyValued, many_valued::ManyValued, "Many-valued";
 ];
 
 macro_rules! make_dyn_logic {
@@ -24,7 +39,13 @@ macro_rules! make_dyn_logic {
         /// - [Classical logic](classical::Classical)
         /// - [Modal logic](modal::Modal)
         /// - [Normal modal logic](normal_modal::NormalModal)
+        /// - [Non-normal modal logic](non_normal::NonNormal)
+        /// - [Tense logic](tense::Tense)
+        /// - [Intuitionist logic](intuitionist::Intuitionist)
+        /// - [Conditional logic](conditional::Conditional)
+        /// - [Many-valued logic](many_valued::ManyValued)
         #[derive(Debug, Clone, PartialEq, Eq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum DynLogic {
             $(
                 $name($logic)
@@ -32,22 +53,33 @@ macro_rules! make_dyn_logic {
         }
 
         #[derive(Debug, Clone, PartialEq, Eq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum DynExpr {
             $(
                 $name(<$logic as Logic>::Expr)
             ),*
         }
 
+        /// Hashes the same as the node it wraps, see [`Branch::with_hash`].
         #[derive(Debug, Clone, PartialEq, Eq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum DynNode {
             $(
                 $name(<$logic as Logic>::Node)
             ),*
         }
 
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum DynCountermodel {
+            $(
+                $name(<$logic as Logic>::Countermodel)
+            ),*
+        }
+
         impl Logic for DynLogic {
             type Expr = DynExpr;
             type Node = DynNode;
+            type Countermodel = DynCountermodel;
 
             fn symbol(&self) -> Cow<'static, str> {
                 match self {
@@ -75,7 +107,7 @@ macro_rules! make_dyn_logic {
                 }
             }
 
-            fn has_contradiction(&self, branch: impl Branch<Self>) -> bool
+            fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId>
             where
                 Self: Sized,
             {
@@ -89,6 +121,34 @@ macro_rules! make_dyn_logic {
                 }
             }
 
+            fn rule_name(&self, node: &Self::Node, inference: InferenceRule<&Self::Node>) -> Cow<'static, str> {
+                match self {
+                    $(
+                        DynLogic::$name(logic) => logic.rule_name(
+                            match node {
+                                DynNode::$name(node) => node,
+                                _ => unreachable!(),
+                            },
+                            inference.map(|node| match node {
+                                DynNode::$name(node) => node,
+                                _ => unreachable!(),
+                            }),
+                        ),
+                    )*
+                }
+            }
+
+            fn countermodel(&self, branch: impl Branch<Self>) -> Self::Countermodel {
+                match self {
+                    $(
+                        DynLogic::$name(logic) => DynCountermodel::$name(logic.countermodel(branch.map(|node| match node {
+                            DynNode::$name(node) => node,
+                            _ => unreachable!(),
+                        }))),
+                    )*
+                }
+            }
+
             fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
                 match self {
                     $(
@@ -111,6 +171,62 @@ macro_rules! make_dyn_logic {
                 }
             }
 
+            fn saturate(&self, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+                match self {
+                    $(
+                        DynLogic::$name(logic) => logic
+                            .saturate(branch.map(|node| match node {
+                                DynNode::$name(node) => node,
+                                _ => unreachable!(),
+                            }))
+                            .map(DynNode::$name),
+                    )*
+                }
+            }
+
+            fn reinfer_on(&self, node: &Self::Node, new_node: &Self::Node) -> bool {
+                match (self, node, new_node) {
+                    $(
+                        (DynLogic::$name(logic), DynNode::$name(node), DynNode::$name(new_node)) => {
+                            logic.reinfer_on(node, new_node)
+                        }
+                    )*
+                    _ => unreachable!(),
+                }
+            }
+
+            fn reinfers(&self) -> bool {
+                match self {
+                    $(DynLogic::$name(logic) => logic.reinfers(),)*
+                }
+            }
+
+            fn place(&self, node: &Self::Node) -> Option<Place> {
+                match self {
+                    $(
+                        DynLogic::$name(logic) => logic.place(match node {
+                            DynNode::$name(node) => node,
+                            _ => unreachable!(),
+                        }),
+                    )*
+                }
+            }
+
+            fn worlds_in(&self, node: &Self::Node) -> usize {
+                match self {
+                    $(
+                        DynLogic::$name(logic) => logic.worlds_in(match node {
+                            DynNode::$name(node) => node,
+                            _ => unreachable!(),
+                        }),
+                    )*
+                }
+            }
+
+            fn owns_node(&self, node: &Self::Node) -> bool {
+                matches!((self, node), $((DynLogic::$name(_), DynNode::$name(_)))|*)
+            }
+
             fn priority(&self, node: &Self::Node) -> u16 {
                 match self {
                     $(
@@ -138,6 +254,40 @@ macro_rules! make_dyn_logic {
             }
         }
 
+        #[cfg(feature = "parse")]
+        impl DynLogic {
+            /// Parses an expression of the underlying logic.
+            pub fn parse_expr(&self, expr: &str) -> Result<DynExpr, ParseError> {
+                Ok(match self {
+                    $(DynLogic::$name(_) => DynExpr::$name(expr.parse()?)),*
+                })
+            }
+        }
+
+        impl Hash for DynNode {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                match self {
+                    $(DynNode::$name(node) => node.hash(state)),*
+                }
+            }
+        }
+
+        impl Print for DynExpr {
+            fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+                match self {
+                    $(DynExpr::$name(expr) => expr.write_with(printer, f)),*
+                }
+            }
+        }
+
+        impl Print for DynNode {
+            fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+                match self {
+                    $(DynNode::$name(node) => node.write_with(printer, f)),*
+                }
+            }
+        }
+
         impl fmt::Display for DynExpr {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                 match self {
@@ -154,12 +304,155 @@ macro_rules! make_dyn_logic {
             }
         }
 
+        impl fmt::Display for DynCountermodel {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    $(DynCountermodel::$name(countermodel) => countermodel.fmt(f)),*
+                }
+            }
+        }
+
     };
 }
 
 // To be able to use it at the top.
 pub(crate) use make_dyn_logic;
 
+impl FromStr for DynLogic {
+    type Err = UnknownLogicError;
+
+    /// Parses a logic by its name, ignoring case:
+    ///
+    /// - `classical` (or `PL`) for [classical logic](classical::Classical).
+    /// - `K` for [modal logic](modal::Modal).
+    /// - `T`, `D`, `B`, `S4` and `S5` for the usual [normal modal
+    ///   logics](normal_modal).
+    /// - `K` followed by frame conditions for any other normal modal logic,
+    ///   such as `Kτ` or `Kρσ`, as given by [`Logic::symbol`]. The conditions
+    ///   can also be written in ASCII as `r`, `s`, `t` and `e`, except that
+    ///   `Kt` alone is rejected, as it could mean either `Kτ` or `Kₜ`.
+    /// - `N`, `S2` and `S3` for the [non-normal modal logics](non_normal),
+    ///   or `N` followed by `ρ` and `τ` (or `r` and `t`).
+    /// - `Kₜ` (or `tense`) for [tense logic](tense), followed by any of
+    ///   `+FC`, `+BC`, `+D` and `+L` for its frame conditions.
+    /// - `I` (or `intuitionist`) for [intuitionist logic](intuitionist).
+    /// - `C` and `C+` for the [conditional logics](conditional).
+    /// - `K3`, `Ł3` (or `L3`), `LP` and `RM3` for the [many-valued
+    ///   logics](many_valued).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::{
+    ///     DynLogic, Logic, conditional, intuitionist::Intuitionist, many_valued, non_normal,
+    ///     normal_modal, tense,
+    /// };
+    ///
+    /// assert_eq!("s4".parse(), Ok(DynLogic::NormalModal(normal_modal::S4)));
+    /// assert_eq!("Kρτ".parse(), Ok(DynLogic::NormalModal(normal_modal::S4)));
+    /// assert_eq!("Krt".parse(), Ok(DynLogic::NormalModal(normal_modal::S4)));
+    /// assert_eq!("Nρ".parse(), Ok(DynLogic::NonNormal(non_normal::S2)));
+    /// assert_eq!("Kₜ+D".parse(), Ok(DynLogic::Tense(tense::DENSE)));
+    /// assert_eq!("tense+fc+l".parse(), Ok(DynLogic::Tense(tense::LINEAR)));
+    /// assert_eq!("tense+fc+l".parse::<DynLogic>().unwrap().symbol(), "Kₜ+L");
+    /// assert!("Kt".parse::<DynLogic>().is_err());
+    /// assert_eq!("I".parse(), Ok(DynLogic::Intuitionist(Intuitionist {})));
+    /// assert_eq!("c+".parse(), Ok(DynLogic::Conditional(conditional::C_PLUS)));
+    /// assert_eq!("Ł3".parse(), Ok(DynLogic::ManyValued(many_valued::L3)));
+    /// assert!("S7".parse::<DynLogic>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let logic = match s.to_lowercase().as_str() {
+            "classical" | "pl" => DynLogic::Classical(classical::Classical {}),
+            "i" | "intuitionist" => DynLogic::Intuitionist(intuitionist::Intuitionist {}),
+            "c" => DynLogic::Conditional(conditional::C),
+            "c+" => DynLogic::Conditional(conditional::C_PLUS),
+            "k3" => DynLogic::ManyValued(many_valued::K3),
+            "ł3" | "l3" => DynLogic::ManyValued(many_valued::L3),
+            "lp" => DynLogic::ManyValued(many_valued::LP),
+            "rm3" => DynLogic::ManyValued(many_valued::RM3),
+            "k" => DynLogic::Modal(modal::Modal {}),
+            "t" => DynLogic::NormalModal(normal_modal::T),
+            "d" => DynLogic::NormalModal(normal_modal::D),
+            "b" => DynLogic::NormalModal(normal_modal::B),
+            "s4" => DynLogic::NormalModal(normal_modal::S4),
+            "s5" => DynLogic::NormalModal(normal_modal::S5),
+            "s2" => DynLogic::NonNormal(non_normal::S2),
+            "s3" => DynLogic::NonNormal(non_normal::S3),
+            // Either `Kτ` or `Kₜ` without its subscript.
+            "kt" => return Err(UnknownLogicError(s.into())),
+            name if name.starts_with("kₜ") || name.starts_with("tense") => {
+                // Every condition comes after a `+`.
+                let mut conditions = name
+                    .trim_start_matches("kₜ")
+                    .trim_start_matches("tense")
+                    .split('+');
+                if conditions.next() != Some("") {
+                    return Err(UnknownLogicError(s.into()));
+                }
+
+                let mut logic = tense::Tense::new();
+                for condition in conditions {
+                    logic = match condition {
+                        "fc" => logic.forward_convergent(),
+                        "bc" => logic.backward_convergent(),
+                        "d" => logic.dense(),
+                        "l" => logic.linear(),
+                        _ => return Err(UnknownLogicError(s.into())),
+                    }
+                }
+
+                DynLogic::Tense(logic)
+            }
+            name if name.starts_with('n') => {
+                let mut logic = non_normal::NonNormal::new();
+                for condition in name.chars().skip(1) {
+                    logic = match condition {
+                        'ρ' | 'r' => logic.reflexive(),
+                        'τ' | 't' => logic.transitive(),
+                        _ => return Err(UnknownLogicError(s.into())),
+                    }
+                }
+
+                DynLogic::NonNormal(logic)
+            }
+            name => {
+                let conditions = name
+                    .strip_prefix('k')
+                    .ok_or_else(|| UnknownLogicError(s.into()))?;
+
+                let mut logic = normal_modal::NormalModal::new();
+                for condition in conditions.chars() {
+                    logic = match condition {
+                        'ρ' | 'r' => logic.reflexive(),
+                        'σ' | 's' => logic.symmetric(),
+                        'τ' | 't' => logic.transitive(),
+                        'η' | 'e' => logic.extendable(),
+                        _ => return Err(UnknownLogicError(s.into())),
+                    }
+                }
+
+                DynLogic::NormalModal(logic)
+            }
+        };
+
+        Ok(logic)
+    }
+}
+
+/// Error returned when parsing a [`DynLogic`] with a name that doesn't match
+/// any logic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLogicError(pub Box<str>);
+
+impl fmt::Display for UnknownLogicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown logic `{}`", self.0)
+    }
+}
+
+impl std::error::Error for UnknownLogicError {}
+
 #[cfg(feature = "wasm")]
 mod wasm {
     #[wasm_bindgen(start)]
@@ -168,12 +461,13 @@ mod wasm {
     }
 
     use crate::{
-        PartialTableau,
+        InferenceStatus, Limits, PartialTableau,
         logic::{
-            DynExpr, DynLogic, DynNode, Logic, classical::Classical, modal::Modal,
-            normal_modal::NormalModal,
+            DynLogic, DynNode, Logic, classical::Classical, conditional::Conditional,
+            intuitionist::Intuitionist, many_valued, modal::Modal, non_normal::NonNormal,
+            normal_modal::NormalModal, tense::Tense,
         },
-        tableau::NodeId,
+        tableau::{DeathReason, NodeId},
     };
     use wasm_bindgen::prelude::*;
 
@@ -202,14 +496,6 @@ mod wasm {
             self.logic.symbol().to_string()
         }
 
-        fn parse_expr(&self, expr: &str) -> Result<DynExpr, String> {
-            Ok(match self.logic {
-                DynLogic::Classical(_) => DynExpr::Classical(expr.parse()?),
-                DynLogic::Modal(_) => DynExpr::Modal(expr.parse()?),
-                DynLogic::NormalModal(_) => DynExpr::NormalModal(expr.parse()?),
-            })
-        }
-
         pub fn tableau(
             &self,
             premises: Vec<String>,
@@ -220,9 +506,17 @@ mod wasm {
         {
             let premises = premises
                 .into_iter()
-                .map(|s| self.parse_expr(&s))
+                .enumerate()
+                .map(|(i, s)| {
+                    self.logic
+                        .parse_expr(&s)
+                        .map_err(|err| format!("in premise {}: {err}", i + 1))
+                })
                 .collect::<Result<Vec<_>, _>>()?;
-            let conclusion = self.parse_expr(conclusion)?;
+            let conclusion = self
+                .logic
+                .parse_expr(conclusion)
+                .map_err(|err| format!("in conclusion: {err}"))?;
 
             Ok(DynPartialTableau {
                 tableau: PartialTableau::new(self.logic.clone(), premises, conclusion),
@@ -257,12 +551,67 @@ mod wasm {
                 }),
             }
         }
+
+        #[wasm_bindgen(js_name = nonNormal)]
+        pub fn non_normal(reflexive: bool, transitive: bool) -> Self {
+            DynLogicWasm {
+                logic: DynLogic::NonNormal(NonNormal {
+                    reflexive,
+                    transitive,
+                }),
+            }
+        }
+
+        pub fn intuitionist() -> Self {
+            DynLogicWasm {
+                logic: DynLogic::Intuitionist(Intuitionist {}),
+            }
+        }
+
+        pub fn conditional(plus: bool) -> Self {
+            DynLogicWasm {
+                logic: DynLogic::Conditional(Conditional { plus }),
+            }
+        }
+
+        /// One of the many-valued logics, by name (`K3`, `Ł3`, `LP` or
+        /// `RM3`).
+        #[wasm_bindgen(js_name = manyValued)]
+        pub fn many_valued(name: &str) -> Result<DynLogicWasm, String> {
+            let logic = match name.to_lowercase().as_str() {
+                "k3" => many_valued::K3,
+                "ł3" | "l3" => many_valued::L3,
+                "lp" => many_valued::LP,
+                "rm3" => many_valued::RM3,
+                _ => return Err(format!("unknown many-valued logic `{name}`")),
+            };
+
+            Ok(DynLogicWasm {
+                logic: DynLogic::ManyValued(logic),
+            })
+        }
+
+        pub fn tense(
+            forward_convergent: bool,
+            backward_convergent: bool,
+            dense: bool,
+            linear: bool,
+        ) -> Self {
+            DynLogicWasm {
+                logic: DynLogic::Tense(Tense {
+                    forward_convergent,
+                    backward_convergent,
+                    dense,
+                    linear,
+                }),
+            }
+        }
     }
 
     #[wasm_bindgen(js_class = Tableau)]
     impl DynPartialTableau {
         #[wasm_bindgen(js_name = inferNode)]
-        pub fn infer_node(&mut self, node_id: u16) -> bool {
+        pub fn infer_node(&mut self, node_id: u32) -> bool {
             self.tableau.infer_node(NodeId { index: node_id }).is_some()
         }
 
@@ -275,26 +624,52 @@ mod wasm {
             while self.infer_once() {}
         }
 
+        /// Infers until the tableau is finished or one of the given limits
+        /// is reached (see [`PartialTableau::infer_with_limits`]).
+        ///
+        /// Returns `true` if the tableau is finished.
+        #[wasm_bindgen(js_name = inferWithLimits)]
+        pub fn infer_with_limits(
+            &mut self,
+            max_nodes: Option<u32>,
+            max_depth: Option<u32>,
+            max_worlds: Option<u32>,
+            max_steps: Option<u32>,
+        ) -> bool {
+            let limits = Limits {
+                max_nodes: max_nodes.map(|max| max as usize),
+                max_depth: max_depth.map(|max| max as usize),
+                max_worlds: max_worlds.map(|max| max as usize),
+                max_steps,
+                deadline: None,
+            };
+
+            !matches!(
+                self.tableau.infer_with_limits(&limits),
+                InferenceStatus::OutOfBudget(_)
+            )
+        }
+
         pub fn inferred(mut self) -> Self {
             self.infer();
             self
         }
 
-        pub fn root(&self) -> u16 {
+        pub fn root(&self) -> u32 {
             self.tableau.root.index
         }
 
-        pub fn get(&self, id: u16) -> DynNodeWasm {
+        pub fn get(&self, id: u32) -> DynNodeWasm {
             DynNodeWasm {
                 node: self.tableau.get(NodeId { index: id }).value.clone(),
             }
         }
 
-        pub fn depth(&self) -> u16 {
+        pub fn depth(&self) -> u32 {
             self.tableau.depth()
         }
 
-        pub fn children(&self, id: u16) -> Box<[u16]> {
+        pub fn children(&self, id: u32) -> Box<[u32]> {
             self.tableau
                 .get(NodeId { index: id })
                 .children
@@ -304,26 +679,74 @@ mod wasm {
         }
 
         #[wasm_bindgen(js_name = toString)]
+        #[allow(clippy::inherent_to_string)]
         pub fn to_string(&self) -> String {
             self.tableau.to_string()
         }
 
         #[wasm_bindgen(js_name = isDead)]
-        pub fn is_dead(&self, node_id: u16) -> bool {
+        pub fn is_dead(&self, node_id: u32) -> bool {
             self.tableau
                 .get(NodeId { index: node_id })
                 .death_reason
                 .is_some()
         }
 
+        /// The id of the node that the given node contradicts, if it closes
+        /// its branch that way.
+        pub fn contradicts(&self, node_id: u32) -> Option<u32> {
+            match self.tableau.get(NodeId { index: node_id }).death_reason {
+                Some(DeathReason::Contradiction { ancestor }) => Some(ancestor.index),
+                _ => None,
+            }
+        }
+
+        /// Where the given node comes from, such as "from 3 by ¬⊃", if it was
+        /// inferred.
+        pub fn justification(&self, node_id: u32) -> Option<String> {
+            self.tableau
+                .get(NodeId { index: node_id })
+                .justification
+                .as_ref()
+                .map(|justification| justification.to_string())
+        }
+
+        /// The id of the node the given node was inferred from, if any.
+        #[wasm_bindgen(js_name = justificationSource)]
+        pub fn justification_source(&self, node_id: u32) -> Option<u32> {
+            self.tableau
+                .get(NodeId { index: node_id })
+                .justification
+                .as_ref()
+                .map(|justification| justification.source.index)
+        }
+
         pub fn holds(&self) -> bool {
             self.tableau.get(self.tableau.root).live_children == 0
         }
+
+        /// The whole tableau as JSON, so that it can be read in one go
+        /// instead of node by node, or saved and loaded with
+        /// [`Self::from_json`].
+        #[cfg(feature = "serde")]
+        #[wasm_bindgen(js_name = toJson)]
+        pub fn to_json(&self) -> Result<String, String> {
+            serde_json::to_string(&self.tableau).map_err(|err| err.to_string())
+        }
+
+        #[cfg(feature = "serde")]
+        #[wasm_bindgen(js_name = fromJson)]
+        pub fn from_json(json: &str) -> Result<DynPartialTableau, String> {
+            serde_json::from_str(json)
+                .map(|tableau| DynPartialTableau { tableau })
+                .map_err(|err| err.to_string())
+        }
     }
 
     #[wasm_bindgen(js_class = Node)]
     impl DynNodeWasm {
         #[wasm_bindgen(js_name = toString)]
+        #[allow(clippy::inherent_to_string)]
         pub fn to_string(&self) -> String {
             self.node.to_string()
         }
@@ -1,10 +1,13 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::str::FromStr;
 
 use crate::logic::InferenceRule;
-use crate::logic::lexer::Symbol;
-use crate::tableau::{Branch, Tableau};
+use crate::logic::{
+    Construct, Grammar, Inspect, Model, ParseError, Printer, Propositional, Symbol, Term,
+};
+use crate::tableau::{Branch, NodeId, Tableau};
 use crate::{Logic, PartialTableau};
 
 #[cfg(feature = "wasm")]
@@ -12,6 +15,7 @@ use wasm_bindgen::prelude::*;
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Classical {}
 
 pub fn infer(input: &str) -> Tableau<Classical> {
@@ -21,43 +25,33 @@ pub fn infer(input: &str) -> Tableau<Classical> {
 impl Logic for Classical {
     type Node = Expr;
     type Expr = Expr;
+    type Countermodel = Countermodel;
 
     fn symbol(&self) -> Cow<'static, str> {
         Cow::Borrowed("")
     }
 
     fn infer(&self, node: &Expr, _branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
-        use InferenceRule as IR;
-        match node {
-            Expr::Const(_) => IR::None,
-            Expr::Not(p) => match p.as_ref() {
-                Expr::Const(_) => IR::none(),
-                Expr::Not(p) => IR::single(*p.clone()),
-                Expr::And(p, q) => IR::split(p.not(), q.not()),
-                Expr::Or(p, q) => IR::chain(vec![p.not(), q.not()]),
-                Expr::MatImpl(p, q) => IR::chain(vec![*p.clone(), q.not()]),
-                Expr::MatEquiv(p, q) => {
-                    IR::split_and_chain([p.not(), *q.clone()], [*p.clone(), q.not()])
-                }
-            },
-            Expr::And(p, q) => IR::chain(vec![*p.clone(), *q.clone()]),
-            Expr::Or(p, q) => IR::split(*p.clone(), *q.clone()),
-            Expr::MatImpl(p, q) => IR::split(p.not(), *q.clone()),
-            Expr::MatEquiv(p, q) => {
-                IR::split_and_chain([*p.clone(), *q.clone()], [p.not(), q.not()])
-            }
-        }
+        node.propositional_inference()
+            .expect("every classical expression has a propositional rule")
     }
 
-    fn has_contradiction(&self, branch: impl Branch<Self>) -> bool {
-        let Some((name, value)) = branch.leaf().interpretation() else {
-            return false;
-        };
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        branch.position(&branch.leaf().complement()?)
+    }
 
-        branch
-            .ancestors()
-            .filter_map(|ancestor| ancestor.interpretation())
-            .any(|(other_name, other_value)| other_name == name && other_value != value)
+    fn rule_name(&self, expr: &Expr, _inference: InferenceRule<&Expr>) -> Cow<'static, str> {
+        Cow::Borrowed(expr.rule_name())
+    }
+
+    fn countermodel(&self, branch: impl Branch<Self>) -> Countermodel {
+        Countermodel {
+            valuation: branch
+                .ancestors()
+                .filter_map(|node| node.literal())
+                .map(|(name, value)| (Box::from(name), value))
+                .collect(),
+        }
     }
 
     fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
@@ -110,7 +104,17 @@ impl Classical {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A valuation read off an open branch, which makes the premises true and the
+/// conclusion false.
+///
+/// Atoms that don't appear in the branch can take any value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Countermodel {
+    pub valuation: BTreeMap<Box<str>, bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expr {
     // TODO: Use some kind of small string type
     Const(Box<str>),
@@ -121,78 +125,118 @@ pub enum Expr {
     MatEquiv(Box<Expr>, Box<Expr>),
 }
 
-impl Expr {
-    fn not(self: &Box<Expr>) -> Expr {
-        Expr::Not(self.clone())
+impl Propositional for Expr {
+    fn atom(name: &str) -> Self {
+        Self::Const(name.into())
     }
 
-    fn interpretation(&self) -> Option<(&str, bool)> {
+    fn not(&self) -> Self {
+        Self::Not(Box::new(self.clone()))
+    }
+}
+
+impl Inspect for Expr {
+    fn inspect(&self) -> Term<'_, Self> {
         match self {
-            Self::Const(name) => Some((name, true)),
-            Self::Not(p) => match p.as_ref() {
-                Self::Const(name) => Some((name, false)),
-                _ => None,
-            },
-            _ => None,
+            Self::Const(name) => Term::Constant(name),
+            Self::Not(p) => Term::Unary(Symbol::Not, p),
+            Self::And(x, y) => Term::Binary(Symbol::And, x, y),
+            Self::Or(x, y) => Term::Binary(Symbol::Or, x, y),
+            Self::MatImpl(x, y) => Term::Binary(Symbol::MatImpl, x, y),
+            Self::MatEquiv(x, y) => Term::Binary(Symbol::MatEquiv, x, y),
         }
     }
 }
 
 impl fmt::Display for Expr {
+    /// Prints the expression in unicode notation, see [`Printer`].
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Const(name) => f.write_str(&name),
-            Self::Not(p) => write!(f, "¬{p}"),
-            Self::And(x, y) => write!(f, "({x} ∧ {y})"),
-            Self::Or(x, y) => write!(f, "({x} ∨ {y})"),
-            Self::MatImpl(x, y) => write!(f, "({x} ⊃ {y})"),
-            Self::MatEquiv(x, y) => write!(f, "({x} ≡ {y})"),
+        Printer::new().write(f, self)
+    }
+}
+
+impl Model for Countermodel {
+    type Expr = Expr;
+    type World = ();
+
+    /// Whether `expr` is true under the valuation, where atoms without a
+    /// value are false.
+    fn evaluate(&self, expr: &Expr, (): ()) -> bool {
+        match expr {
+            Expr::Const(name) => self.valuation.get(name).copied().unwrap_or(false),
+            Expr::Not(p) => !self.evaluate(p, ()),
+            Expr::And(p, q) => self.evaluate(p, ()) && self.evaluate(q, ()),
+            Expr::Or(p, q) => self.evaluate(p, ()) || self.evaluate(q, ()),
+            Expr::MatImpl(p, q) => !self.evaluate(p, ()) || self.evaluate(q, ()),
+            Expr::MatEquiv(p, q) => self.evaluate(p, ()) == self.evaluate(q, ()),
         }
     }
 }
 
+impl fmt::Display for Countermodel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.valuation.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "v({name}) = {}", u8::from(*value))?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "parse")]
 impl FromStr for Expr {
-    // TODO: It would be really nice to just return the proper error :/
-    type Err = String;
-
+    type Err = ParseError;
+
+    /// Parses an expression with the default [`Grammar`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::classical::Expr;
+    ///
+    /// // `∧` binds tighter than `∨`, which binds tighter than `⊃`.
+    /// assert_eq!("p ∧ q ∨ r".parse::<Expr>(), "(p ∧ q) ∨ r".parse());
+    /// assert_eq!("¬p ∨ q ⊃ r".parse::<Expr>(), "((¬p) ∨ q) ⊃ r".parse());
+    /// assert_eq!("p ⊃ q ⊃ r".parse::<Expr>(), "p ⊃ (q ⊃ r)".parse());
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use crate::logic::lexer::{Symbol, ident};
-        use winnow::{
-            ModalResult, Parser,
-            ascii::space0,
-            combinator::alt,
-            combinator::{delimited, preceded},
-            seq,
-        };
-
-        fn expr_single<'a>(input: &mut &'a str) -> ModalResult<Expr> {
-            let main = alt((
-                delimited('(', expr, ')'),
-                ident.map(|name: &str| Expr::Const(name.to_string().into_boxed_str())),
-                preceded(Symbol::Not.parser(), expr_single).map(|expr| Expr::Not(Box::new(expr))),
-            ));
-
-            delimited(space0, main, space0).parse_next(input)
-        }
+        Self::parse_with(s, &Grammar::new())
+    }
+}
 
-        fn expr<'a>(input: &mut &'a str) -> ModalResult<Expr> {
-            let main = alt((
-                seq!(expr_single, Symbol::And.parser(), expr)
-                    .map(|(a, _, b)| Expr::And(Box::new(a), Box::new(b))),
-                seq!(expr_single, Symbol::Or.parser(), expr)
-                    .map(|(a, _, b)| Expr::Or(Box::new(a), Box::new(b))),
-                seq!(expr_single, Symbol::MatImpl.parser(), expr)
-                    .map(|(a, _, b)| Expr::MatImpl(Box::new(a), Box::new(b))),
-                seq!(expr_single, Symbol::MatEquiv.parser(), expr)
-                    .map(|(a, _, b)| Expr::MatEquiv(Box::new(a), Box::new(b))),
-                expr_single,
-            ));
-
-            delimited(space0, main, space0).parse_next(input)
+#[cfg(feature = "parse")]
+impl Expr {
+    /// Parses an expression, grouping chained connectives as given by
+    /// `grammar`.
+    pub fn parse_with(s: &str, grammar: &Grammar) -> Result<Self, ParseError> {
+        crate::logic::parse(s, Classical::symbols(), grammar)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Construct for Expr {
+    fn constant(name: &str) -> Self {
+        Expr::Const(name.into())
+    }
+
+    fn unary(symbol: Symbol, operand: Self) -> Self {
+        match symbol {
+            Symbol::Not => Expr::Not(Box::new(operand)),
+            _ => unreachable!("{symbol:?} isn't a classical operator"),
         }
+    }
 
-        // TODO: It would be really nice to just return the proper error :/
-        expr.parse(s).map_err(|err| err.to_string())
+    fn binary(symbol: Symbol, lhs: Self, rhs: Self) -> Self {
+        let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+        match symbol {
+            Symbol::And => Expr::And(lhs, rhs),
+            Symbol::Or => Expr::Or(lhs, rhs),
+            Symbol::MatImpl => Expr::MatImpl(lhs, rhs),
+            Symbol::MatEquiv => Expr::MatEquiv(lhs, rhs),
+            _ => unreachable!("{symbol:?} isn't a classical connective"),
+        }
     }
 }
@@ -0,0 +1,614 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+use crate::{
+    Logic, PartialTableau,
+    logic::{
+        Construct, Grammar, InferenceRule, Inspect, Model, Notation, ParseError, Place, Print,
+        Printer, Propositional, Symbol, Term, modal::World,
+    },
+    tableau::{Branch, NodeId, Tableau},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The conditional logics C and C+ of Priest's chapter 5, with a conditional
+/// `A ≻ B` (`A => B` in ASCII) that is true at a world when `B` is true at
+/// every world where things are as they would be if `A` were the case.
+///
+/// Those worlds are given by a relation for each formula, `w R_A w'`, so
+/// relations on the branch are labelled with their antecedent, as in
+/// `0 r_A 1`. In C they can be any worlds at all, so not even `A ≻ A` holds.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::{PartialTableau, logic::conditional};
+///
+/// for argument in ["⊢ p ≻ p", "p, p ≻ q ⊢ q"] {
+///     let tableau = PartialTableau::parse_with(conditional::C, argument, str::parse).unwrap();
+///     assert!(!tableau.infer().holds());
+///
+///     let tableau = PartialTableau::parse_with(conditional::C_PLUS, argument, str::parse).unwrap();
+///     assert!(tableau.infer().holds());
+/// }
+///
+/// // Conditionals aren't transitive, even in C+.
+/// let argument = "p ≻ q, q ≻ r ⊢ p ≻ r";
+/// let tableau = PartialTableau::parse_with(conditional::C_PLUS, argument, str::parse).unwrap();
+/// assert!(!tableau.infer().holds());
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conditional {
+    /// C+, where `A` is true at every world `w'` such that `w R_A w'`, and
+    /// if `A` is true at `w` then `w R_A w`
+    pub plus: bool,
+}
+
+/// C
+pub const C: Conditional = Conditional::new();
+
+/// C+
+pub const C_PLUS: Conditional = Conditional::new().plus();
+
+pub fn infer(input: &str) -> Tableau<Conditional> {
+    PartialTableau::from_str(input).unwrap().infer()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Node {
+    /// `A, i`.
+    Expr { expr: Expr, world: World },
+    /// `i r_A j`, world `j` is one of those where things are as they would
+    /// be at world `i` if `A` were the case.
+    Relation {
+        from: World,
+        antecedent: Expr,
+        to: World,
+    },
+}
+
+/// A model of conditional logic, with an accessibility relation for each
+/// formula and a valuation for each world.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConditionalModel {
+    pub worlds: BTreeSet<World>,
+    /// Triples `(w1, A, w2)` such that `w1 R_A w2`.
+    pub relation: BTreeSet<(World, Expr, World)>,
+    /// Truth value of the atoms at each world. Atoms that don't appear at a
+    /// world can take any value.
+    pub valuation: BTreeMap<World, BTreeMap<Box<str>, bool>>,
+}
+
+impl Logic for Conditional {
+    type Node = Node;
+    type Expr = Expr;
+    type Countermodel = ConditionalModel;
+
+    fn symbol(&self) -> Cow<'static, str> {
+        Cow::Borrowed(if self.plus { "C+" } else { "C" })
+    }
+
+    fn infer(&self, node: &Self::Node, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        use InferenceRule as IR;
+
+        let (expr, world) = match node {
+            Node::Expr { expr, world } => (expr, *world),
+            // In C+, `A` is true at every world that `r_A` reaches.
+            Node::Relation { antecedent, to, .. } if self.plus => {
+                return IR::single(Node::Expr {
+                    expr: antecedent.clone(),
+                    world: *to,
+                });
+            }
+            Node::Relation { .. } => return IR::none(),
+        };
+
+        if let Some(inference) = expr.propositional_inference() {
+            return inference.map(|expr| Node::Expr { expr, world });
+        }
+
+        match expr {
+            Expr::Conditional(p, q) => IR::chain(
+                branch
+                    .relations_from(world)
+                    .filter_map(|relation| relation.accessible_world_from(world, p))
+                    .map(|other_world| Node::Expr {
+                        expr: *q.clone(),
+                        world: other_world,
+                    })
+                    .collect(),
+            ),
+            Expr::Not(conditional) => {
+                let Expr::Conditional(p, q) = conditional.as_ref() else {
+                    unreachable!("{expr} has a propositional rule");
+                };
+
+                let fresh_world = branch.fresh_world();
+
+                IR::chain(vec![
+                    Node::Relation {
+                        from: world,
+                        antecedent: *p.clone(),
+                        to: fresh_world,
+                    },
+                    Node::Expr {
+                        expr: q.not(),
+                        world: fresh_world,
+                    },
+                ])
+            }
+            _ => unreachable!("{expr} has a propositional rule"),
+        }
+    }
+
+    fn saturate(&self, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        if !self.plus {
+            return InferenceRule::none();
+        }
+
+        let worlds = branch
+            .ancestors()
+            .filter_map(|node| match node {
+                Node::Expr { world, .. } => Some(*world),
+                Node::Relation { .. } => None,
+            })
+            .collect::<BTreeSet<_>>();
+        let antecedents = branch
+            .ancestors()
+            .filter_map(Node::antecedent)
+            .collect::<BTreeSet<_>>();
+
+        // Centering, every world where `A` is true accesses itself through
+        // `r_A`, so either `A` is false there or `i r_A i`.
+        for &world in &worlds {
+            for &antecedent in &antecedents {
+                let relation = Node::Relation {
+                    from: world,
+                    antecedent: antecedent.clone(),
+                    to: world,
+                };
+                let false_node = Node::Expr {
+                    expr: antecedent.not(),
+                    world,
+                };
+                if branch.contains(&relation) || branch.contains(&false_node) {
+                    continue;
+                }
+
+                let true_node = Node::Expr {
+                    expr: antecedent.clone(),
+                    world,
+                };
+                if branch.contains(&true_node) {
+                    return InferenceRule::single(relation);
+                }
+
+                return InferenceRule::split(false_node, relation);
+            }
+        }
+
+        InferenceRule::none()
+    }
+
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        branch.position(&branch.leaf().complement()?)
+    }
+
+    fn rule_name(
+        &self,
+        node: &Self::Node,
+        inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        // Centering is the only rule that yields a relation without a fresh
+        // world.
+        if let InferenceRule::Single(Node::Relation { .. })
+        | InferenceRule::Split([_, Node::Relation { .. }]) = inference
+        {
+            return Cow::Borrowed("ρ");
+        }
+
+        match node {
+            Node::Expr { expr, .. } => Cow::Borrowed(expr.rule_name()),
+            Node::Relation { .. } => Cow::Borrowed("r_A"),
+        }
+    }
+
+    fn countermodel(&self, branch: impl Branch<Self>) -> ConditionalModel {
+        let mut model = ConditionalModel::default();
+        for node in branch.ancestors() {
+            match node {
+                Node::Expr { world, .. } => {
+                    model.worlds.insert(*world);
+                    model.valuation.entry(*world).or_default();
+                }
+                Node::Relation {
+                    from,
+                    antecedent,
+                    to,
+                } => {
+                    model.worlds.extend([*from, *to]);
+                    model.relation.insert((*from, antecedent.clone(), *to));
+                }
+            }
+
+            if let Some((name, value, world)) = node.interpretation() {
+                model
+                    .valuation
+                    .entry(world)
+                    .or_default()
+                    .insert(Box::from(name), value);
+            }
+        }
+
+        model
+    }
+
+    fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
+        Node::Expr {
+            expr,
+            world: World::ZERO,
+        }
+    }
+
+    fn make_conclusion_node(&self, expr: Self::Expr) -> Self::Node {
+        Node::Expr {
+            expr: expr.not(),
+            world: World::ZERO,
+        }
+    }
+
+    fn reinfers(&self) -> bool {
+        true
+    }
+
+    fn reinfer_on(&self, node: &Self::Node, new_node: &Self::Node) -> bool {
+        // `A ≻ B` has to be applied to every world that `r_A` reaches.
+        match node {
+            Node::Expr {
+                expr: Expr::Conditional(p, _),
+                world,
+            } => new_node.accessible_world_from(*world, p).is_some(),
+            _ => false,
+        }
+    }
+
+    fn worlds_in(&self, node: &Self::Node) -> usize {
+        node.max_world().index() as usize + 1
+    }
+
+    fn place(&self, node: &Self::Node) -> Option<Place> {
+        Some(match node {
+            Node::Expr { world, .. } => Place::At(*world),
+            Node::Relation { from, to, .. } => Place::Relation {
+                from: *from,
+                to: *to,
+            },
+        })
+    }
+
+    fn priority(&self, node: &Self::Node) -> u16 {
+        match node {
+            Node::Expr { expr, .. } => match expr {
+                Expr::Const(_) => 5,
+                Expr::Not(p) if matches!(p.as_ref(), Expr::Conditional(_, _)) => 1000,
+                Expr::Not(_) => 5,
+                Expr::And(_, _) => 7,
+                Expr::Or(_, _) => 5,
+                Expr::MatImpl(_, _) => 5,
+                Expr::MatEquiv(_, _) => 2,
+                Expr::Conditional(_, _) => 0,
+            },
+            Node::Relation { .. } => 100,
+        }
+    }
+}
+
+impl Conditional {
+    /// Creates C, the basic conditional logic.
+    pub const fn new() -> Self {
+        Self { plus: false }
+    }
+
+    pub const fn plus(self) -> Self {
+        Self { plus: true }
+    }
+
+    /// Symbols used in conditional logic.
+    pub const fn symbols() -> &'static [Symbol] {
+        &[
+            Symbol::Not,
+            Symbol::And,
+            Symbol::Or,
+            Symbol::MatImpl,
+            Symbol::MatEquiv,
+            Symbol::Conditional,
+        ]
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Conditional {
+    /// Symbols used in conditional logic.
+    #[wasm_bindgen(js_name = symbols)]
+    pub fn symbols_wasm() -> Vec<Symbol> {
+        Self::symbols().to_vec()
+    }
+}
+
+impl Node {
+    /// The highest world index in the node.
+    fn max_world(&self) -> World {
+        match self {
+            Self::Expr { world, .. } => *world,
+            Self::Relation { from, to, .. } => *from.max(to),
+        }
+    }
+
+    /// The antecedent of the conditional in the node, if it's `A ≻ B, i` or
+    /// `¬(A ≻ B), i`.
+    fn antecedent(&self) -> Option<&Expr> {
+        let Self::Expr { expr, .. } = self else {
+            return None;
+        };
+
+        match expr {
+            Expr::Conditional(p, _) => Some(p),
+            Expr::Not(p) => match p.as_ref() {
+                Expr::Conditional(p, _) => Some(p),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// What world can the node access through `r_A`, assuming we start from
+    /// the given world.
+    ///
+    /// Returns `None` if the node is not such a relation or if the given
+    /// world is not the source world.
+    pub fn accessible_world_from(&self, world: World, antecedent: &Expr) -> Option<World> {
+        match self {
+            Self::Relation {
+                from,
+                antecedent: other,
+                to,
+            } if *from == world && other == antecedent => Some(*to),
+            _ => None,
+        }
+    }
+
+    /// The node that contradicts this one, if it's a literal (such as
+    /// `¬p, 1` for `p, 1`).
+    pub fn complement(&self) -> Option<Self> {
+        match self {
+            Self::Expr { expr, world } => Some(Self::Expr {
+                expr: expr.complement()?,
+                world: *world,
+            }),
+            Self::Relation { .. } => None,
+        }
+    }
+
+    /// Interpretation of a variable, if any, as a `(name, truth_value, world)`
+    /// tuple.
+    pub fn interpretation(&self) -> Option<(&str, bool, World)> {
+        match self {
+            Self::Expr { expr, world } => {
+                let (name, value) = expr.literal()?;
+                Some((name, value, *world))
+            }
+            Self::Relation { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expr {
+    Const(Box<str>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    MatImpl(Box<Expr>, Box<Expr>),
+    MatEquiv(Box<Expr>, Box<Expr>),
+    /// `A ≻ B`, if it were the case that `A`, it would be the case that `B`.
+    Conditional(Box<Expr>, Box<Expr>),
+}
+
+impl Propositional for Expr {
+    fn atom(name: &str) -> Self {
+        Self::Const(name.into())
+    }
+
+    fn not(&self) -> Self {
+        Self::Not(Box::new(self.clone()))
+    }
+}
+
+impl Model for ConditionalModel {
+    type Expr = Expr;
+    type World = World;
+
+    /// Whether `expr` is true at `world`, where `A ≻ B` looks at the worlds
+    /// that `world` accesses through `R_A`.
+    ///
+    /// Atoms without a value at `world` are taken to be false.
+    fn evaluate(&self, expr: &Expr, world: World) -> bool {
+        match expr {
+            Expr::Const(name) => self
+                .valuation
+                .get(&world)
+                .and_then(|valuation| valuation.get(name))
+                .copied()
+                .unwrap_or(false),
+            Expr::Not(p) => !self.evaluate(p, world),
+            Expr::And(p, q) => self.evaluate(p, world) && self.evaluate(q, world),
+            Expr::Or(p, q) => self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatImpl(p, q) => !self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatEquiv(p, q) => self.evaluate(p, world) == self.evaluate(q, world),
+            Expr::Conditional(p, q) => self
+                .relation
+                .iter()
+                .filter(|(from, antecedent, _)| *from == world && antecedent == p.as_ref())
+                .all(|(_, _, to)| self.evaluate(q, *to)),
+        }
+    }
+}
+
+impl fmt::Display for ConditionalModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "W = {{")?;
+        for (i, world) in self.worlds.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{world}")?;
+        }
+        writeln!(f, "}}")?;
+
+        write!(f, "R = {{")?;
+        for (i, (from, antecedent, to)) in self.relation.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write_relation(&Printer::new(), f, *from, antecedent, *to)?;
+        }
+        writeln!(f, "}}")?;
+
+        for (world, valuation) in &self.valuation {
+            for (name, value) in valuation {
+                writeln!(f, "v_{world}({name}) = {}", u8::from(*value))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes `from r_A to`, with the antecedent in parentheses (or braces in
+/// LaTeX) unless it's a constant or a unary operator applied to something.
+fn write_relation(
+    printer: &Printer,
+    f: &mut dyn fmt::Write,
+    from: World,
+    antecedent: &Expr,
+    to: World,
+) -> fmt::Result {
+    let (open, close) = match (printer.notation, antecedent.inspect()) {
+        (Notation::Latex, _) => ("{", "}"),
+        (_, Term::Binary(..)) => ("(", ")"),
+        (_, Term::Constant(_) | Term::Unary(..)) => ("", ""),
+    };
+
+    write!(f, "{from} r_{open}")?;
+    printer.write(f, antecedent)?;
+    write!(f, "{close} {to}")
+}
+
+impl Inspect for Expr {
+    fn inspect(&self) -> Term<'_, Self> {
+        match self {
+            Self::Const(name) => Term::Constant(name),
+            Self::Not(p) => Term::Unary(Symbol::Not, p),
+            Self::And(x, y) => Term::Binary(Symbol::And, x, y),
+            Self::Or(x, y) => Term::Binary(Symbol::Or, x, y),
+            Self::MatImpl(x, y) => Term::Binary(Symbol::MatImpl, x, y),
+            Self::MatEquiv(x, y) => Term::Binary(Symbol::MatEquiv, x, y),
+            Self::Conditional(x, y) => Term::Binary(Symbol::Conditional, x, y),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Prints the expression in unicode notation, see [`Printer`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+impl Print for Node {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Node::Expr { expr, world } => {
+                printer.write(f, expr)?;
+                write!(f, ", {world}")
+            }
+            Node::Relation {
+                from,
+                antecedent,
+                to,
+            } => write_relation(printer, f, *from, antecedent, *to),
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    /// Parses an expression with the default [`Grammar`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::conditional::Expr;
+    ///
+    /// // `≻` binds like `⊃`, and is `=>` in ASCII since `>` is `⊃`.
+    /// assert_eq!("p ∧ q ≻ r".parse::<Expr>(), "(p ∧ q) ≻ r".parse());
+    /// assert_eq!("p => q > r".parse::<Expr>(), "p ≻ (q ⊃ r)".parse());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Grammar::new())
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Expr {
+    /// Parses an expression, grouping chained connectives as given by
+    /// `grammar`.
+    pub fn parse_with(s: &str, grammar: &Grammar) -> Result<Self, ParseError> {
+        crate::logic::parse(s, Conditional::symbols(), grammar)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Construct for Expr {
+    fn constant(name: &str) -> Self {
+        Expr::Const(name.into())
+    }
+
+    fn unary(symbol: Symbol, operand: Self) -> Self {
+        match symbol {
+            Symbol::Not => Expr::Not(Box::new(operand)),
+            _ => unreachable!("{symbol:?} isn't a conditional logic operator"),
+        }
+    }
+
+    fn binary(symbol: Symbol, lhs: Self, rhs: Self) -> Self {
+        let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+        match symbol {
+            Symbol::And => Expr::And(lhs, rhs),
+            Symbol::Or => Expr::Or(lhs, rhs),
+            Symbol::MatImpl => Expr::MatImpl(lhs, rhs),
+            Symbol::MatEquiv => Expr::MatEquiv(lhs, rhs),
+            Symbol::Conditional => Expr::Conditional(lhs, rhs),
+            _ => unreachable!("{symbol:?} isn't a conditional logic connective"),
+        }
+    }
+}
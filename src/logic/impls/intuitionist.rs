@@ -0,0 +1,471 @@
+use std::{borrow::Cow, fmt, ops, str::FromStr};
+
+use crate::{
+    Logic, PartialTableau,
+    logic::{
+        InferenceRule, Model, Notation, Place, Print, Printer, Symbol,
+        classical::{Classical, Expr},
+        modal::{KripkeModel, World},
+    },
+    tableau::{Branch, NodeId, Tableau},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The intuitionist logic I of Priest's chapter 6, with the same formulas as
+/// [classical logic](Classical).
+///
+/// Formulas are true (`A, +i`) or not true (`A, −i`) at the worlds, which are
+/// states of information. The relation is reflexive and transitive, true
+/// atoms stay true at every later world, and `¬A` and `A ⊃ B` are about every
+/// later world as well, so something that doesn't hold yet might still do.
+///
+/// A new world is only created for `¬A, −i` or `A ⊃ B, −i` when no world that
+/// `i` accesses already has what it needs, which keeps tableaux small.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::{PartialTableau, logic::{classical::Classical, intuitionist::Intuitionist}};
+///
+/// for argument in ["⊢ p ∨ ¬p", "¬¬p ⊢ p", "⊢ ((p ⊃ q) ⊃ p) ⊃ p"] {
+///     let tableau = argument.parse::<PartialTableau<Classical>>().unwrap();
+///     assert!(tableau.infer().holds());
+///
+///     let tableau = argument.parse::<PartialTableau<Intuitionist>>().unwrap();
+///     assert!(!tableau.infer().holds());
+/// }
+///
+/// let tableau = "⊢ p ⊃ ¬¬p".parse::<PartialTableau<Intuitionist>>().unwrap();
+/// assert!(tableau.infer().holds());
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Intuitionist {}
+
+pub fn infer(input: &str) -> Tableau<Intuitionist> {
+    PartialTableau::from_str(input).unwrap().infer()
+}
+
+/// Whether a formula is true at a world or not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sign {
+    /// `+`, the formula is true.
+    Plus,
+    /// `−`, the formula isn't true, which doesn't make its negation true.
+    Minus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Node {
+    /// `A, +i` or `A, −i`.
+    Expr {
+        expr: Expr,
+        sign: Sign,
+        world: World,
+    },
+    /// `irj`, world `j` is at least as informed as world `i`.
+    Relation { from: World, to: World },
+}
+
+/// A [`KripkeModel`] of intuitionist logic, where atoms without a value are
+/// false.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntuitionistModel {
+    pub kripke: KripkeModel,
+}
+
+impl Logic for Intuitionist {
+    type Node = Node;
+    type Expr = Expr;
+    type Countermodel = IntuitionistModel;
+
+    fn symbol(&self) -> Cow<'static, str> {
+        Cow::Borrowed("I")
+    }
+
+    fn infer(&self, node: &Self::Node, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        use InferenceRule as IR;
+        use Sign::{Minus, Plus};
+
+        let (expr, sign, world) = match node {
+            Node::Expr { expr, sign, world } => (expr, *sign, *world),
+            // We have `j r k`, so every `i r j` needs an `i r k`.
+            Node::Relation { from: j, to: k } => {
+                return IR::chain(
+                    branch
+                        .relations_to(*j)
+                        .filter_map(|other| match other {
+                            Node::Relation { from: i, .. } => Some(relation(*i, *k)),
+                            Node::Expr { .. } => None,
+                        })
+                        .collect(),
+                );
+            }
+        };
+
+        let at = |expr: &Expr, sign| signed(expr.clone(), sign, world);
+        match (expr, sign) {
+            // Heredity, true atoms stay true.
+            (Expr::Const(_), Plus) => IR::chain(
+                accessible(&branch, world)
+                    .map(|other_world| signed(expr.clone(), Plus, other_world))
+                    .collect(),
+            ),
+            (Expr::Const(_), Minus) => IR::none(),
+            (Expr::And(p, q), Plus) => IR::chain(vec![at(p, Plus), at(q, Plus)]),
+            (Expr::And(p, q), Minus) => IR::split(at(p, Minus), at(q, Minus)),
+            (Expr::Or(p, q), Plus) => IR::split(at(p, Plus), at(q, Plus)),
+            (Expr::Or(p, q), Minus) => IR::chain(vec![at(p, Minus), at(q, Minus)]),
+            (Expr::Not(p), Plus) => IR::chain(
+                accessible(&branch, world)
+                    .map(|other_world| signed(*p.clone(), Minus, other_world))
+                    .collect(),
+            ),
+            (Expr::Not(p), Minus) => witness(&branch, world, [(p, Plus)]),
+            // Splits on one world at a time, and gets inferred again for the
+            // next one once a side is added, see `Self::reinfer_on`. Worlds
+            // where a side already follows by heredity are skipped.
+            (Expr::MatImpl(p, q), Plus) => {
+                let other_world = accessible(&branch, world).find(|&other_world| {
+                    !follows(&branch, p, Minus, other_world)
+                        && !follows(&branch, q, Plus, other_world)
+                });
+
+                match other_world {
+                    Some(other_world) => IR::split(
+                        signed(*p.clone(), Minus, other_world),
+                        signed(*q.clone(), Plus, other_world),
+                    ),
+                    None => IR::none(),
+                }
+            }
+            (Expr::MatImpl(p, q), Minus) => witness(&branch, world, [(p, Plus), (q, Minus)]),
+            // `A ≡ B` is `(A ⊃ B) ∧ (B ⊃ A)`.
+            (Expr::MatEquiv(p, q), Plus) => {
+                IR::chain(vec![at(&implies(p, q), Plus), at(&implies(q, p), Plus)])
+            }
+            (Expr::MatEquiv(p, q), Minus) => {
+                IR::split(at(&implies(p, q), Minus), at(&implies(q, p), Minus))
+            }
+        }
+    }
+
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        let Node::Expr { expr, sign, world } = branch.leaf() else {
+            return None;
+        };
+
+        branch.position(&signed(expr.clone(), !*sign, *world))
+    }
+
+    fn rule_name(
+        &self,
+        node: &Self::Node,
+        _inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        use Sign::{Minus, Plus};
+
+        let Node::Expr { expr, sign, .. } = node else {
+            return Cow::Borrowed("τ");
+        };
+
+        Cow::Borrowed(match (expr, sign) {
+            (Expr::Const(_), Plus) => "H",
+            (Expr::Const(_), Minus) => "",
+            (Expr::Not(_), Plus) => "¬+",
+            (Expr::Not(_), Minus) => "¬−",
+            (Expr::And(_, _), Plus) => "∧+",
+            (Expr::And(_, _), Minus) => "∧−",
+            (Expr::Or(_, _), Plus) => "∨+",
+            (Expr::Or(_, _), Minus) => "∨−",
+            (Expr::MatImpl(_, _), Plus) => "⊃+",
+            (Expr::MatImpl(_, _), Minus) => "⊃−",
+            (Expr::MatEquiv(_, _), Plus) => "≡+",
+            (Expr::MatEquiv(_, _), Minus) => "≡−",
+        })
+    }
+
+    fn countermodel(&self, branch: impl Branch<Self>) -> IntuitionistModel {
+        let mut kripke = KripkeModel::default();
+        for node in branch.ancestors() {
+            match node {
+                Node::Expr { expr, sign, world } => {
+                    kripke.worlds.insert(*world);
+                    let valuation = kripke.valuation.entry(*world).or_default();
+                    if let Expr::Const(name) = expr {
+                        valuation.insert(name.clone(), *sign == Sign::Plus);
+                    }
+                }
+                Node::Relation { from, to } => {
+                    kripke.worlds.extend([*from, *to]);
+                    kripke.relation.insert((*from, *to));
+                }
+            }
+        }
+
+        IntuitionistModel { kripke }
+    }
+
+    fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
+        signed(expr, Sign::Plus, World::ZERO)
+    }
+
+    fn make_conclusion_node(&self, expr: Self::Expr) -> Self::Node {
+        signed(expr, Sign::Minus, World::ZERO)
+    }
+
+    fn initialize(tableau: &mut PartialTableau<Self>) {
+        // Every other world accesses itself from the rule that creates it.
+        for leaf in tableau.live_leaves() {
+            tableau.add_child(leaf, relation(World::ZERO, World::ZERO));
+        }
+    }
+
+    fn reinfers(&self) -> bool {
+        true
+    }
+
+    fn reinfer_on(&self, node: &Self::Node, new_node: &Self::Node) -> bool {
+        use Sign::{Minus, Plus};
+
+        match (node, new_node) {
+            // Heredity, `¬A, +i` and `A ⊃ B, +i` apply to every new `i r j`.
+            (
+                Node::Expr {
+                    expr: Expr::Const(_) | Expr::Not(_) | Expr::MatImpl(_, _),
+                    sign: Plus,
+                    world,
+                },
+                Node::Relation { from, .. },
+            ) => world == from,
+            // `A ⊃ B, +i` split on some world, so it moves on to the next one.
+            (
+                Node::Expr {
+                    expr: Expr::MatImpl(p, _),
+                    sign: Plus,
+                    ..
+                },
+                Node::Expr {
+                    expr, sign: Minus, ..
+                },
+            ) => expr == p.as_ref(),
+            (
+                Node::Expr {
+                    expr: Expr::MatImpl(_, q),
+                    sign: Plus,
+                    ..
+                },
+                Node::Expr {
+                    expr, sign: Plus, ..
+                },
+            ) => expr == q.as_ref(),
+            // We have `j r k` and a new `i r j`, so we need `i r k`.
+            (Node::Relation { from: j, .. }, Node::Relation { to: j_new, .. }) => j == j_new,
+            _ => false,
+        }
+    }
+
+    fn worlds_in(&self, node: &Self::Node) -> usize {
+        match node {
+            Node::Expr { world, .. } => world.index() as usize + 1,
+            Node::Relation { from, to } => from.index().max(to.index()) as usize + 1,
+        }
+    }
+
+    fn place(&self, node: &Self::Node) -> Option<Place> {
+        Some(match node {
+            Node::Expr { world, .. } => Place::At(*world),
+            Node::Relation { from, to } => Place::Relation {
+                from: *from,
+                to: *to,
+            },
+        })
+    }
+
+    fn priority(&self, node: &Self::Node) -> u16 {
+        use Sign::{Minus, Plus};
+
+        let Node::Expr { expr, sign, .. } = node else {
+            return 10;
+        };
+
+        // Splits go last, and `A ⊃ B, +i` last of all since it splits once
+        // for every world.
+        match (expr, sign) {
+            (Expr::Const(_), _) => 9,
+            (Expr::And(_, _), Plus) | (Expr::Or(_, _), Minus) => 8,
+            (Expr::Not(_), Plus) | (Expr::MatEquiv(_, _), Plus) => 8,
+            (Expr::Not(_), Minus) | (Expr::MatImpl(_, _), Minus) => 6,
+            (Expr::And(_, _), Minus) | (Expr::Or(_, _), Plus) => 5,
+            (Expr::MatEquiv(_, _), Minus) => 5,
+            (Expr::MatImpl(_, _), Plus) => 4,
+        }
+    }
+}
+
+impl Intuitionist {
+    /// Symbols used in intuitionist logic.
+    pub const fn symbols() -> &'static [Symbol] {
+        Classical::symbols()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Intuitionist {
+    /// Symbols used in intuitionist logic.
+    #[wasm_bindgen(js_name = symbols)]
+    pub fn symbols_wasm() -> Vec<Symbol> {
+        Self::symbols().to_vec()
+    }
+}
+
+fn signed(expr: Expr, sign: Sign, world: World) -> Node {
+    Node::Expr { expr, sign, world }
+}
+
+fn relation(from: World, to: World) -> Node {
+    Node::Relation { from, to }
+}
+
+fn implies(p: &Expr, q: &Expr) -> Expr {
+    Expr::MatImpl(Box::new(p.clone()), Box::new(q.clone()))
+}
+
+/// The worlds that `world` accesses in the branch, including itself.
+fn accessible<'b>(
+    branch: &'b impl Branch<Intuitionist>,
+    world: World,
+) -> impl Iterator<Item = World> + 'b {
+    branch
+        .relations_from(world)
+        .filter_map(|relation| match relation {
+            Node::Relation { to, .. } => Some(*to),
+            Node::Expr { .. } => None,
+        })
+}
+
+/// Whether `expr, +world` follows from the branch by heredity, i.e., whether
+/// the branch has `expr, +i` for some `i r world`, or likewise whether
+/// `expr, −world` follows from some `expr, −j` with `world r j`.
+fn follows(branch: &impl Branch<Intuitionist>, expr: &Expr, sign: Sign, world: World) -> bool {
+    match sign {
+        Sign::Plus => branch.relations_to(world).any(|relation| match relation {
+            Node::Relation { from, .. } => branch.contains(&signed(expr.clone(), sign, *from)),
+            Node::Expr { .. } => false,
+        }),
+        Sign::Minus => branch.relations_from(world).any(|relation| match relation {
+            Node::Relation { to, .. } => branch.contains(&signed(expr.clone(), sign, *to)),
+            Node::Expr { .. } => false,
+        }),
+    }
+}
+
+/// A world accessible from `world` with the given signed formulas, which is
+/// a fresh one unless some world on the branch already has them.
+fn witness<const N: usize>(
+    branch: &impl Branch<Intuitionist>,
+    world: World,
+    formulas: [(&Expr, Sign); N],
+) -> InferenceRule<Node> {
+    let witnessed = accessible(branch, world).any(|other_world| {
+        formulas
+            .iter()
+            .all(|&(expr, sign)| branch.contains(&signed(expr.clone(), sign, other_world)))
+    });
+    if witnessed {
+        return InferenceRule::none();
+    }
+
+    let fresh_world = branch.fresh_world();
+
+    let relations = [
+        relation(world, fresh_world),
+        relation(fresh_world, fresh_world),
+    ];
+    let formulas = formulas
+        .into_iter()
+        .map(|(expr, sign)| signed(expr.clone(), sign, fresh_world));
+
+    InferenceRule::chain(relations.into_iter().chain(formulas).collect())
+}
+
+impl ops::Not for Sign {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        match self {
+            Self::Plus => Self::Minus,
+            Self::Minus => Self::Plus,
+        }
+    }
+}
+
+impl Model for IntuitionistModel {
+    type Expr = Expr;
+    type World = World;
+
+    /// Whether `expr` is true at `world`, where `¬A` and `A ⊃ B` look at every
+    /// world that `world` accesses.
+    ///
+    /// Atoms without a value at `world` are false.
+    fn evaluate(&self, expr: &Expr, world: World) -> bool {
+        match expr {
+            Expr::Const(name) => self
+                .kripke
+                .valuation
+                .get(&world)
+                .and_then(|valuation| valuation.get(name))
+                .copied()
+                .unwrap_or(false),
+            Expr::Not(p) => self
+                .kripke
+                .accessible_from(world)
+                .all(|other| !self.evaluate(p, other)),
+            Expr::And(p, q) => self.evaluate(p, world) && self.evaluate(q, world),
+            Expr::Or(p, q) => self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatImpl(p, q) => self
+                .kripke
+                .accessible_from(world)
+                .all(|other| !self.evaluate(p, other) || self.evaluate(q, other)),
+            Expr::MatEquiv(p, q) => self
+                .kripke
+                .accessible_from(world)
+                .all(|other| self.evaluate(p, other) == self.evaluate(q, other)),
+        }
+    }
+}
+
+impl Print for Node {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Node::Expr { expr, sign, world } => {
+                printer.write(f, expr)?;
+                let sign = match (sign, printer.notation) {
+                    (Sign::Plus, _) => "+",
+                    (Sign::Minus, Notation::Unicode) => "−",
+                    (Sign::Minus, Notation::Ascii | Notation::Latex) => "-",
+                };
+                write!(f, ", {sign}{world}")
+            }
+            Node::Relation { from, to } => write!(f, "{from}r{to}"),
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+impl fmt::Display for IntuitionistModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kripke.fmt(f)
+    }
+}
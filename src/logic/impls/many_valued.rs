@@ -0,0 +1,720 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt, str::FromStr};
+
+use crate::{
+    Logic, PartialTableau,
+    logic::{
+        Construct, Grammar, InferenceRule, Inspect, Model, Notation, ParseError, Print, Printer,
+        Symbol, Term, intuitionist::Sign,
+    },
+    tableau::{Branch, NodeId, Tableau},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The three-valued logics of Priest's chapter 7, given by the truth tables
+/// of their connectives and the set of designated values.
+///
+/// Nodes are signed: `A, +` says that the value of `A` is designated and
+/// `A, −` that it isn't. The rules of each connective are read off its truth
+/// table, with the value of each operand told apart by the signs of it and
+/// its negation, which gives Priest's rules for [K3] and [LP]. A branch
+/// closes when a formula has both signs, or when an atom and its negation
+/// have signs that no value gives them.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::{PartialTableau, logic::many_valued};
+///
+/// let holds = |logic, argument| {
+///     let tableau = PartialTableau::parse_with(logic, argument, str::parse).unwrap();
+///     tableau.infer().holds()
+/// };
+///
+/// // Gaps in K3 and gluts in LP.
+/// assert!(!holds(many_valued::K3, "⊢ p ∨ ¬p"));
+/// assert!(holds(many_valued::LP, "⊢ p ∨ ¬p"));
+/// assert!(holds(many_valued::K3, "p, ¬p ⊢ q"));
+/// assert!(!holds(many_valued::LP, "p, ¬p ⊢ q"));
+///
+/// // Ł3 has a conditional of its own, unlike K3.
+/// assert!(!holds(many_valued::K3, "⊢ p ⊃ p"));
+/// assert!(holds(many_valued::L3, "⊢ p → p"));
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManyValued {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub tables: TruthTables,
+    /// Whether each value is designated, indexed by [`Value`].
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub designated: [bool; 3],
+}
+
+/// The values of three-valued logics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value {
+    /// `0`, false.
+    Zero,
+    /// `½`, neither true nor false in K3 and Ł3, and both in LP and RM3.
+    Half,
+    /// `1`, true.
+    One,
+}
+
+/// The value of a binary connective, indexed by the values of its left and
+/// right operands.
+pub type Table = [[Value; 3]; 3];
+
+/// The truth tables of the connectives of a [`ManyValued`] logic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TruthTables {
+    /// Indexed by the value of the operand.
+    pub not: [Value; 3],
+    pub and: Table,
+    pub or: Table,
+    pub mat_impl: Table,
+    pub mat_equiv: Table,
+    /// `A → B`, the logic's own conditional.
+    pub implication: Table,
+}
+
+const Z: Value = Value::Zero;
+const H: Value = Value::Half;
+const O: Value = Value::One;
+
+/// The strong Kleene tables, where `A ⊃ B` is `¬A ∨ B` and there is no other
+/// conditional, so `A → B` is `A ⊃ B` as well.
+const KLEENE: TruthTables = TruthTables {
+    not: [O, H, Z],
+    and: [[Z, Z, Z], [Z, H, H], [Z, H, O]],
+    or: [[Z, H, O], [H, H, O], [O, O, O]],
+    mat_impl: [[O, O, O], [H, H, O], [Z, H, O]],
+    mat_equiv: [[O, H, Z], [H, H, H], [Z, H, O]],
+    implication: [[O, O, O], [H, H, O], [Z, H, O]],
+};
+
+/// K3, the strong Kleene logic, where only `1` is designated.
+pub const K3: ManyValued = ManyValued {
+    tables: KLEENE,
+    designated: [false, false, true],
+};
+
+/// Ł3, Łukasiewicz's logic, which is K3 with a conditional that is true when
+/// both sides are `½`.
+pub const L3: ManyValued = ManyValued {
+    tables: TruthTables {
+        implication: [[O, O, O], [H, O, O], [Z, H, O]],
+        ..KLEENE
+    },
+    ..K3
+};
+
+/// LP, the logic of paradox, where `½` is designated as well.
+pub const LP: ManyValued = ManyValued {
+    tables: KLEENE,
+    designated: [false, true, true],
+};
+
+/// RM3, which is LP with a conditional that is false whenever the value
+/// goes down.
+pub const RM3: ManyValued = ManyValued {
+    tables: TruthTables {
+        implication: [[O, O, O], [Z, H, O], [Z, Z, O]],
+        ..KLEENE
+    },
+    ..LP
+};
+
+/// Infers the tableau of the argument in [K3].
+pub fn infer(input: &str) -> Tableau<ManyValued> {
+    PartialTableau::parse_with(K3, input, str::parse)
+        .unwrap()
+        .infer()
+}
+
+/// `A, +` or `A, −`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    pub expr: Expr,
+    pub sign: Sign,
+}
+
+/// A valuation read off an open branch, under which the premises are
+/// designated and the conclusion isn't.
+///
+/// Atoms that don't appear in the branch can take any value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManyValuedModel {
+    /// The logic whose truth tables evaluate formulas.
+    pub logic: ManyValued,
+    pub valuation: BTreeMap<Box<str>, Value>,
+}
+
+impl Logic for ManyValued {
+    type Node = Node;
+    type Expr = Expr;
+    type Countermodel = ManyValuedModel;
+
+    fn symbol(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match *self {
+            logic if logic == K3 => "K3",
+            logic if logic == L3 => "Ł3",
+            logic if logic == LP => "LP",
+            logic if logic == RM3 => "RM3",
+            _ => "3V",
+        })
+    }
+
+    fn infer(&self, node: &Self::Node, _branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        self.rule(node)
+    }
+
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        let leaf = branch.leaf();
+        if let Some(node_id) = branch.position(&signed(leaf.expr.clone(), !leaf.sign)) {
+            return Some(node_id);
+        }
+
+        // The values that the atom can still take, narrowed down by every
+        // node about it until there are none left.
+        let (name, _) = leaf.literal()?;
+        let mut values = Value::ALL
+            .into_iter()
+            .filter(|&value| self.allows(leaf, value))
+            .collect::<Vec<_>>();
+        for (node_id, node) in branch.ancestors_with_id() {
+            if node.literal().is_some_and(|(other, _)| other == name) {
+                values.retain(|&value| self.allows(node, value));
+                if values.is_empty() {
+                    return Some(node_id);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn rule_name(
+        &self,
+        node: &Self::Node,
+        _inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        let name = node.expr.rule_name();
+        if name.is_empty() {
+            return Cow::Borrowed("");
+        }
+
+        Cow::Owned(match node.sign {
+            Sign::Plus => format!("{name}+"),
+            Sign::Minus => format!("{name}−"),
+        })
+    }
+
+    fn countermodel(&self, branch: impl Branch<Self>) -> ManyValuedModel {
+        let mut valuation = BTreeMap::new();
+        for node in branch.ancestors() {
+            let Some((name, _)) = node.literal() else {
+                continue;
+            };
+            if valuation.contains_key(name) {
+                continue;
+            }
+
+            // `½` first, as in Priest's countermodels for K3 and LP.
+            let value = [Value::Half, Value::One, Value::Zero]
+                .into_iter()
+                .find(|&value| {
+                    branch
+                        .ancestors()
+                        .filter(|other| other.literal().is_some_and(|(other, _)| other == name))
+                        .all(|other| self.allows(other, value))
+                })
+                .expect("open branches have a value for every atom");
+            valuation.insert(Box::from(name), value);
+        }
+
+        ManyValuedModel {
+            logic: *self,
+            valuation,
+        }
+    }
+
+    fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
+        signed(expr, Sign::Plus)
+    }
+
+    fn make_conclusion_node(&self, expr: Self::Expr) -> Self::Node {
+        signed(expr, Sign::Minus)
+    }
+
+    fn priority(&self, node: &Self::Node) -> u16 {
+        // Rules with fewer branches go first.
+        match self.rule(node) {
+            InferenceRule::None => 10,
+            InferenceRule::Single(_) | InferenceRule::Chain(_) => 9,
+            InferenceRule::Split(_) | InferenceRule::SplitAndChain(_) => 7,
+            InferenceRule::SplitChains(chains) => 9u16.saturating_sub(chains.len() as u16),
+        }
+    }
+}
+
+impl ManyValued {
+    /// Symbols used in many-valued logics.
+    pub const fn symbols() -> &'static [Symbol] {
+        &[
+            Symbol::Not,
+            Symbol::And,
+            Symbol::Or,
+            Symbol::MatImpl,
+            Symbol::MatEquiv,
+            Symbol::Implication,
+        ]
+    }
+
+    pub const fn is_designated(&self, value: Value) -> bool {
+        self.designated[value as usize]
+    }
+
+    /// Whether `node` holds when its atom has the given value.
+    fn allows(&self, node: &Node, value: Value) -> bool {
+        let value = match node.literal() {
+            Some((_, true)) => self.tables.not[value as usize],
+            _ => value,
+        };
+
+        self.is_designated(value) == (node.sign == Sign::Plus)
+    }
+
+    /// The rule for `node`, read off the truth table of its connective.
+    ///
+    /// The value of the expression depends on the values of one or two
+    /// operands (its parts). Each branch of the rule is a set of signs for
+    /// the parts and their negations, such that every combination of values
+    /// with those signs gives the expression the sign of `node`, and every
+    /// combination that does so has the signs of some branch.
+    fn rule(&self, node: &Node) -> InferenceRule<Node> {
+        let not = |value: Value| self.tables.not[value as usize];
+
+        // A binary connective, possibly negated, or `¬¬A`, the negation of
+        // `¬A`.
+        let (expr, negated) = match &node.expr {
+            Expr::Not(p) => (p.as_ref(), true),
+            expr => (expr, false),
+        };
+        let (parts, table) = match expr {
+            Expr::Const(_) => return InferenceRule::none(),
+            Expr::Not(p) => (vec![p.as_ref()], None),
+            expr => {
+                let (table, lhs, rhs) = self.table(expr).expect("binary connective");
+                (vec![lhs, rhs], Some(table))
+            }
+        };
+        let value = |values: &[Value]| {
+            let value = match table {
+                Some(table) => table[values[0] as usize][values[1] as usize],
+                None => not(values[0]),
+            };
+            if negated { not(value) } else { value }
+        };
+
+        // Every combination of values of the parts, with the signs they give
+        // each part and its negation.
+        let combinations = (0..3usize.pow(parts.len() as u32))
+            .map(|mut index| {
+                let values = parts
+                    .iter()
+                    .map(|_| {
+                        let value = Value::ALL[index % 3];
+                        index /= 3;
+                        value
+                    })
+                    .collect::<Vec<_>>();
+                let signs = values
+                    .iter()
+                    .flat_map(|&value| [self.sign_of(value), self.sign_of(not(value))])
+                    .collect::<Vec<_>>();
+                let wanted = self.sign_of(value(&values)) == node.sign;
+                (signs, wanted)
+            })
+            .collect::<Vec<_>>();
+
+        let branches = cover(&combinations);
+        if branches.iter().any(Vec::is_empty) {
+            return InferenceRule::none();
+        }
+        if branches.is_empty() {
+            // No values give the expression this sign.
+            let part = parts[0].clone();
+            return InferenceRule::split_chains(vec![vec![
+                signed(part.clone(), Sign::Plus),
+                signed(part, Sign::Minus),
+            ]]);
+        }
+
+        let mut branches = branches
+            .into_iter()
+            .map(|signs| {
+                signs
+                    .into_iter()
+                    .map(|(index, sign)| {
+                        let part = parts[index / 2];
+                        let expr = if index % 2 == 0 {
+                            part.clone()
+                        } else {
+                            part.not()
+                        };
+                        signed(expr, sign)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Splits are always given as chains, which aren't split when the
+        // branch already has one of them.
+        match (branches.len(), branches[0].len()) {
+            (1, 1) => InferenceRule::single(branches.remove(0).remove(0)),
+            (1, _) => InferenceRule::chain(branches.remove(0)),
+            _ => InferenceRule::split_chains(branches),
+        }
+    }
+
+    /// The truth table of a binary connective and its operands.
+    fn table<'a>(&self, expr: &'a Expr) -> Option<(Table, &'a Expr, &'a Expr)> {
+        let tables = &self.tables;
+        Some(match expr {
+            Expr::And(p, q) => (tables.and, p, q),
+            Expr::Or(p, q) => (tables.or, p, q),
+            Expr::MatImpl(p, q) => (tables.mat_impl, p, q),
+            Expr::MatEquiv(p, q) => (tables.mat_equiv, p, q),
+            Expr::Implication(p, q) => (tables.implication, p, q),
+            Expr::Const(_) | Expr::Not(_) => return None,
+        })
+    }
+
+    fn sign_of(&self, value: Value) -> Sign {
+        if self.is_designated(value) {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl ManyValued {
+    /// Symbols used in many-valued logics.
+    #[wasm_bindgen(js_name = symbols)]
+    pub fn symbols_wasm() -> Vec<Symbol> {
+        Self::symbols().to_vec()
+    }
+}
+
+/// A few sets of signs for the parts of an expression that cover every
+/// combination in `combinations` that is wanted and no other, as pairs of
+/// the index of a sign and the sign.
+///
+/// Sets with fewer signs are preferred, so a set is empty if any combination
+/// will do, and there are none if no combination will.
+fn cover(combinations: &[(Vec<Sign>, bool)]) -> Vec<Vec<(usize, Sign)>> {
+    let Some((signs, _)) = combinations.first() else {
+        return Vec::new();
+    };
+
+    let matches = |term: &[Option<Sign>], signs: &[Sign]| {
+        term.iter()
+            .zip(signs)
+            .all(|(term, sign)| term.is_none_or(|term| term == *sign))
+    };
+    let implies = |term: &[Option<Sign>]| {
+        let mut matched = combinations
+            .iter()
+            .filter(|(signs, _)| matches(term, signs))
+            .peekable();
+        matched.peek().is_some() && matched.all(|(_, wanted)| *wanted)
+    };
+
+    // Every assignment of `+`, `−` or nothing to each sign, keeping those
+    // that can't be any more general.
+    let terms = (0..3usize.pow(signs.len() as u32))
+        .map(|mut index| {
+            signs
+                .iter()
+                .map(|_| {
+                    let term = [None, Some(Sign::Plus), Some(Sign::Minus)][index % 3];
+                    index /= 3;
+                    term
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|term| {
+            implies(term)
+                && (0..term.len()).filter(|&i| term[i].is_some()).all(|i| {
+                    let mut general = term.clone();
+                    general[i] = None;
+                    !implies(&general)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let mut uncovered = combinations
+        .iter()
+        .filter(|(_, wanted)| *wanted)
+        .map(|(signs, _)| signs)
+        .collect::<Vec<_>>();
+    let mut output = Vec::new();
+    while let Some(first) = uncovered.first() {
+        let best = terms
+            .iter()
+            .map(|term| {
+                let covered = uncovered
+                    .iter()
+                    .filter(|signs| matches(term, signs))
+                    .count();
+                let len = term.iter().flatten().count();
+                (term, len, covered)
+            })
+            .filter(|&(_, _, covered)| covered > 0)
+            .min_by_key(|&(_, len, covered)| (len, usize::MAX - covered));
+
+        // Values that the signs don't tell apart have to be given in full.
+        let term = match best {
+            Some((term, _, _)) => term.clone(),
+            None => first.iter().copied().map(Some).collect(),
+        };
+
+        uncovered.retain(|signs| !matches(&term, signs));
+        output.push(
+            term.into_iter()
+                .enumerate()
+                .filter_map(|(index, sign)| Some((index, sign?)))
+                .collect(),
+        );
+    }
+
+    output
+}
+
+fn signed(expr: Expr, sign: Sign) -> Node {
+    Node { expr, sign }
+}
+
+impl Node {
+    /// The atom of the node, if its expression is `p` or `¬p`, and whether it
+    /// is negated.
+    fn literal(&self) -> Option<(&str, bool)> {
+        match &self.expr {
+            Expr::Const(name) => Some((name, false)),
+            Expr::Not(p) => match p.as_ref() {
+                Expr::Const(name) => Some((name, true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expr {
+    Const(Box<str>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    MatImpl(Box<Expr>, Box<Expr>),
+    MatEquiv(Box<Expr>, Box<Expr>),
+    /// `A → B`, the conditional given by [`TruthTables::implication`].
+    Implication(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn not(&self) -> Self {
+        Self::Not(Box::new(self.clone()))
+    }
+
+    /// The name of the tableau rule that applies to this expression, such as
+    /// "¬∧" or "→", without the sign.
+    fn rule_name(&self) -> &'static str {
+        match self {
+            Expr::Const(_) => "",
+            Expr::Not(p) => match p.as_ref() {
+                Expr::Const(_) => "",
+                Expr::Not(_) => "¬¬",
+                Expr::And(_, _) => "¬∧",
+                Expr::Or(_, _) => "¬∨",
+                Expr::MatImpl(_, _) => "¬⊃",
+                Expr::MatEquiv(_, _) => "¬≡",
+                Expr::Implication(_, _) => "¬→",
+            },
+            Expr::And(_, _) => "∧",
+            Expr::Or(_, _) => "∨",
+            Expr::MatImpl(_, _) => "⊃",
+            Expr::MatEquiv(_, _) => "≡",
+            Expr::Implication(_, _) => "→",
+        }
+    }
+}
+
+impl Value {
+    /// Every value, from lowest to highest.
+    pub const ALL: [Value; 3] = [Value::Zero, Value::Half, Value::One];
+}
+
+impl ManyValuedModel {
+    /// The value of `expr`, as given by the truth tables of the logic.
+    ///
+    /// Atoms without a value are taken to be `0`.
+    pub fn value(&self, expr: &Expr) -> Value {
+        let tables = &self.logic.tables;
+        let binary = |table: &Table, p: &Expr, q: &Expr| {
+            table[self.value(p) as usize][self.value(q) as usize]
+        };
+
+        match expr {
+            Expr::Const(name) => self.valuation.get(name).copied().unwrap_or(Value::Zero),
+            Expr::Not(p) => tables.not[self.value(p) as usize],
+            Expr::And(p, q) => binary(&tables.and, p, q),
+            Expr::Or(p, q) => binary(&tables.or, p, q),
+            Expr::MatImpl(p, q) => binary(&tables.mat_impl, p, q),
+            Expr::MatEquiv(p, q) => binary(&tables.mat_equiv, p, q),
+            Expr::Implication(p, q) => binary(&tables.implication, p, q),
+        }
+    }
+}
+
+impl Model for ManyValuedModel {
+    type Expr = Expr;
+    type World = ();
+
+    /// Whether the value of `expr` is designated.
+    fn evaluate(&self, expr: &Expr, (): ()) -> bool {
+        self.logic.is_designated(self.value(expr))
+    }
+}
+
+impl Inspect for Expr {
+    fn inspect(&self) -> Term<'_, Self> {
+        match self {
+            Self::Const(name) => Term::Constant(name),
+            Self::Not(p) => Term::Unary(Symbol::Not, p),
+            Self::And(x, y) => Term::Binary(Symbol::And, x, y),
+            Self::Or(x, y) => Term::Binary(Symbol::Or, x, y),
+            Self::MatImpl(x, y) => Term::Binary(Symbol::MatImpl, x, y),
+            Self::MatEquiv(x, y) => Term::Binary(Symbol::MatEquiv, x, y),
+            Self::Implication(x, y) => Term::Binary(Symbol::Implication, x, y),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Prints the expression in unicode notation, see [`Printer`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Value::Zero => "0",
+            Value::Half => "½",
+            Value::One => "1",
+        })
+    }
+}
+
+impl Print for Node {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+        printer.write(f, &self.expr)?;
+        let sign = match (self.sign, printer.notation) {
+            (Sign::Plus, _) => "+",
+            (Sign::Minus, Notation::Unicode) => "−",
+            (Sign::Minus, Notation::Ascii | Notation::Latex) => "-",
+        };
+        write!(f, ", {sign}")
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+impl fmt::Display for ManyValuedModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.valuation.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "v({name}) = {value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parse")]
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    /// Parses an expression with the default [`Grammar`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::many_valued::Expr;
+    ///
+    /// // `→` binds like `⊃`, and is `->` in ASCII.
+    /// assert_eq!("p ∧ q → r".parse::<Expr>(), "(p ∧ q) → r".parse());
+    /// assert_eq!("p -> q > r".parse::<Expr>(), "p → (q ⊃ r)".parse());
+    ///
+    /// // Only `->` ends an atom, not `-` on its own.
+    /// assert_eq!("a-b->c".parse::<Expr>(), "(a-b) → c".parse());
+    /// assert_eq!("a-b".parse::<Expr>(), Ok(Expr::Const("a-b".into())));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Grammar::new())
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Expr {
+    /// Parses an expression, grouping chained connectives as given by
+    /// `grammar`.
+    pub fn parse_with(s: &str, grammar: &Grammar) -> Result<Self, ParseError> {
+        crate::logic::parse(s, ManyValued::symbols(), grammar)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Construct for Expr {
+    fn constant(name: &str) -> Self {
+        Expr::Const(name.into())
+    }
+
+    fn unary(symbol: Symbol, operand: Self) -> Self {
+        match symbol {
+            Symbol::Not => Expr::Not(Box::new(operand)),
+            _ => unreachable!("{symbol:?} isn't a many-valued operator"),
+        }
+    }
+
+    fn binary(symbol: Symbol, lhs: Self, rhs: Self) -> Self {
+        let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+        match symbol {
+            Symbol::And => Expr::And(lhs, rhs),
+            Symbol::Or => Expr::Or(lhs, rhs),
+            Symbol::MatImpl => Expr::MatImpl(lhs, rhs),
+            Symbol::MatEquiv => Expr::MatEquiv(lhs, rhs),
+            Symbol::Implication => Expr::Implication(lhs, rhs),
+            _ => unreachable!("{symbol:?} isn't a many-valued connective"),
+        }
+    }
+}
@@ -1,3 +1,9 @@
 pub mod classical;
+pub mod conditional;
+pub mod intuitionist;
+pub mod many_valued;
 pub mod modal;
+pub mod non_normal;
 pub mod normal_modal;
+pub mod tense;
+mod worlds;
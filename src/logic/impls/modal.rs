@@ -1,9 +1,17 @@
-use std::{borrow::Cow, fmt, ops::Deref, str::FromStr};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
 
 use crate::{
     Logic, PartialTableau,
-    logic::{InferenceRule, lexer::Symbol},
-    tableau::{Branch, Tableau},
+    logic::{
+        Construct, Grammar, InferenceRule, Inspect, ParseError, Place, Print, Printer,
+        Propositional, Symbol, Term,
+    },
+    tableau::{Branch, NodeId, Tableau},
 };
 
 #[cfg(feature = "wasm")]
@@ -11,6 +19,7 @@ use wasm_bindgen::prelude::*;
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Modal {}
 
 pub fn infer(input: &str) -> Tableau<Modal> {
@@ -20,6 +29,7 @@ pub fn infer(input: &str) -> Tableau<Modal> {
 impl Logic for Modal {
     type Node = Node;
     type Expr = Expr;
+    type Countermodel = KripkeModel;
 
     fn symbol(&self) -> Cow<'static, str> {
         Cow::Borrowed("K")
@@ -34,35 +44,15 @@ impl Logic for Modal {
         };
 
         let world = *world;
+        if let Some(inference) = expr.propositional_inference() {
+            return inference.map(|expr| Node::Expr { expr, world });
+        }
 
-        let classical_inference: IR<Expr> = match expr {
-            Expr::Const(_) => IR::none(),
-            Expr::Not(p) => match p.as_ref() {
-                Expr::Const(_) => IR::none(),
-                Expr::Not(p) => IR::single(*p.clone()),
-                Expr::And(p, q) => IR::split(p.not(), q.not()),
-                Expr::Or(p, q) => IR::chain(vec![p.not(), q.not()]),
-                Expr::MatImpl(p, q) => IR::chain(vec![*p.clone(), q.not()]),
-                Expr::MatEquiv(p, q) => {
-                    IR::split_and_chain([p.not(), *q.clone()], [*p.clone(), q.not()])
-                }
-                Expr::Possibility(p) => IR::single(Expr::Necessity(Box::new(p.not()))),
-                Expr::Necessity(p) => IR::single(Expr::Possibility(Box::new(p.not()))),
-            },
-            Expr::And(p, q) => IR::chain(vec![*p.clone(), *q.clone()]),
-            Expr::Or(p, q) => IR::split(*p.clone(), *q.clone()),
-            Expr::MatImpl(p, q) => IR::split(p.not(), *q.clone()),
-            Expr::MatEquiv(p, q) => {
-                IR::split_and_chain([*p.clone(), *q.clone()], [p.not(), q.not()])
-            }
+        match expr {
             Expr::Possibility(p) => {
-                let max_so_far = branch
-                    .ancestors()
-                    .filter_map(|ancestor| ancestor.world())
-                    .max();
-                let fresh_world = max_so_far.map_or(World::ZERO, |i| i.next());
+                let fresh_world = branch.fresh_world();
 
-                return IR::chain(vec![
+                IR::chain(vec![
                     Node::Relation {
                         from: world,
                         to: fresh_world,
@@ -71,36 +61,39 @@ impl Logic for Modal {
                         expr: *p.clone(),
                         world: fresh_world,
                     },
-                ]);
-            }
-            Expr::Necessity(p) => {
-                return IR::chain(
-                    branch
-                        .ancestors()
-                        .filter_map(|ancestor| ancestor.accessible_world_from(world))
-                        .map(|other_world| Node::Expr {
-                            expr: *p.clone(),
-                            world: other_world,
-                        })
-                        .collect(),
-                );
+                ])
             }
-        };
+            Expr::Necessity(p) => IR::chain(
+                branch
+                    .relations_from(world)
+                    .filter_map(|relation| relation.accessible_world_from(world))
+                    .map(|other_world| Node::Expr {
+                        expr: *p.clone(),
+                        world: other_world,
+                    })
+                    .collect(),
+            ),
+            _ => unreachable!("{expr} has a propositional rule"),
+        }
+    }
 
-        classical_inference.map(|expr| Node::Expr { expr, world })
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        branch.position(&branch.leaf().complement()?)
     }
 
-    fn has_contradiction(&self, branch: impl Branch<Self>) -> bool {
-        let Some((name, value, world)) = branch.leaf().interpretation() else {
-            return false;
-        };
+    fn rule_name(
+        &self,
+        node: &Self::Node,
+        _inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        match node {
+            Node::Expr { expr, .. } => Cow::Borrowed(expr.rule_name()),
+            Node::Relation { .. } => Cow::Borrowed(""),
+        }
+    }
 
-        branch
-            .ancestors()
-            .filter_map(|ancestor| ancestor.interpretation())
-            .any(|(other_name, other_value, other_world)| {
-                other_name == name && other_world == world && other_value != value
-            })
+    fn countermodel(&self, branch: impl Branch<Self>) -> KripkeModel {
+        KripkeModel::from_branch(branch)
     }
 
     fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
@@ -117,6 +110,29 @@ impl Logic for Modal {
         }
     }
 
+    fn reinfers(&self) -> bool {
+        true
+    }
+
+    fn reinfer_on(&self, node: &Self::Node, new_node: &Self::Node) -> bool {
+        // □ has to be applied to every world that becomes accessible.
+        match node {
+            Node::Expr {
+                expr: Expr::Necessity(_),
+                world,
+            } => new_node.accessible_world_from(*world).is_some(),
+            _ => false,
+        }
+    }
+
+    fn worlds_in(&self, node: &Self::Node) -> usize {
+        node.worlds_in()
+    }
+
+    fn place(&self, node: &Self::Node) -> Option<Place> {
+        Some(node.place())
+    }
+
     fn priority(&self, node: &Self::Node) -> u16 {
         match node {
             Node::Expr { expr, .. } => match expr {
@@ -135,7 +151,7 @@ impl Logic for Modal {
 }
 
 impl Modal {
-    /// Symbols used in classical logic.
+    /// Symbols used in modal logic.
     pub const fn symbols() -> &'static [Symbol] {
         &[
             Symbol::Not,
@@ -152,14 +168,15 @@ impl Modal {
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 impl Modal {
-    /// Symbols used in classical logic.
+    /// Symbols used in modal logic.
     #[wasm_bindgen(js_name = symbols)]
     pub fn symbols_wasm() -> Vec<Symbol> {
         Self::symbols().to_vec()
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expr {
     // TODO: Use some kind of small string type
     Const(Box<str>),
@@ -172,19 +189,35 @@ pub enum Expr {
     Necessity(Box<Expr>),
 }
 
-impl Expr {
-    pub fn not(self: &Box<Self>) -> Self {
-        Self::Not(self.clone())
+impl Propositional for Expr {
+    fn atom(name: &str) -> Self {
+        Self::Const(name.into())
+    }
+
+    fn not(&self) -> Self {
+        Self::Not(Box::new(self.clone()))
+    }
+
+    /// `¬□A` and `¬◇A` just move the negation inwards, to `◇¬A` and `□¬A`.
+    fn negated(&self) -> Option<Self> {
+        match self {
+            Expr::Possibility(p) => Some(Expr::Necessity(Box::new(p.not()))),
+            Expr::Necessity(p) => Some(Expr::Possibility(Box::new(p.not()))),
+            _ => None,
+        }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Node {
-    Expr { expr: Expr, world: World },
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Node<E = Expr> {
+    Expr { expr: E, world: World },
     Relation { from: World, to: World },
 }
 
-impl Node {
+/// Nodes of any logic with worlds, such as [tense logic](super::tense), whose
+/// expressions are other than modal ones.
+impl<E> Node<E> {
     pub fn world(&self) -> Option<World> {
         match self {
             Self::Expr { world, .. } => Some(*world),
@@ -192,6 +225,25 @@ impl Node {
         }
     }
 
+    /// Where the node stands among the worlds, see [`Logic::place`].
+    pub fn place(&self) -> Place {
+        match self {
+            Self::Expr { world, .. } => Place::At(*world),
+            Self::Relation { from, to } => Place::Relation {
+                from: *from,
+                to: *to,
+            },
+        }
+    }
+
+    /// One more than the highest world index in the node.
+    pub fn worlds_in(&self) -> usize {
+        match self {
+            Self::Expr { world, .. } => world.0 as usize + 1,
+            Self::Relation { from, to } => from.0.max(to.0) as usize + 1,
+        }
+    }
+
     /// What world can the node access, assuming we start from the given world.
     ///
     /// Returns `None` if the node is not a relation or if the given world is
@@ -202,6 +254,20 @@ impl Node {
             _ => None,
         }
     }
+}
+
+impl<E: Propositional> Node<E> {
+    /// The node that contradicts this one, if it's a literal (such as
+    /// `¬p, 1` for `p, 1`).
+    pub fn complement(&self) -> Option<Self> {
+        match self {
+            Self::Expr { expr, world } => Some(Self::Expr {
+                expr: expr.complement()?,
+                world: *world,
+            }),
+            Self::Relation { .. } => None,
+        }
+    }
 
     /// Interpretation of a variable, if any.
     ///
@@ -209,106 +275,232 @@ impl Node {
     /// expression, [`None`] otherwise.
     pub fn interpretation(&self) -> Option<(&str, bool, World)> {
         match self {
-            Self::Expr { expr, world } => match expr {
-                Expr::Const(name) => Some((name, true, *world)),
-                Expr::Not(p) => match p.deref() {
-                    Expr::Const(name) => Some((name, false, *world)),
-                    _ => None,
-                },
-                _ => None,
-            },
-            _ => None,
+            Self::Expr { expr, world } => {
+                let (name, value) = expr.literal()?;
+                Some((name, value, *world))
+            }
+            Self::Relation { .. } => None,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct World(u16);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct World(u32);
 
 impl World {
     pub const ZERO: Self = World(0);
 
+    pub const fn new(index: u32) -> Self {
+        World(index)
+    }
+
+    pub const fn index(self) -> u32 {
+        self.0
+    }
+
+    /// The world after this one.
+    ///
+    /// # Panics
+    ///
+    /// If there are no more worlds left, instead of wrapping around.
     pub fn next(&self) -> Self {
-        World(self.0 + 1)
+        World(self.0.checked_add(1).expect("ran out of worlds"))
     }
 }
 
-impl fmt::Display for Expr {
+/// A Kripke model, made of a set of worlds, an accessibility relation and a
+/// valuation for each world.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::logic;
+///
+/// let tableau = logic::modal::infer("◇p, ◇q ⊢ ◇(p ∧ q)");
+/// let countermodels = tableau.countermodels();
+/// assert_eq!(countermodels.len(), 1);
+/// assert_eq!(countermodels[0].worlds.len(), 3);
+/// assert_eq!(countermodels[0].relation.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KripkeModel {
+    pub worlds: BTreeSet<World>,
+    /// Pairs `(w1, w2)` such that `w1 R w2`.
+    pub relation: BTreeSet<(World, World)>,
+    /// Truth value of the atoms at each world. Atoms that don't appear at a
+    /// world can take any value.
+    pub valuation: BTreeMap<World, BTreeMap<Box<str>, bool>>,
+}
+
+impl KripkeModel {
+    /// Reads the model off an open branch, as described in Priest's 2.4.
+    pub(crate) fn from_branch<L: Logic<Node = Node>>(branch: impl Branch<L>) -> Self {
+        Self::from_nodes(branch.ancestors(), |node| node.interpretation())
+    }
+
+    /// Like [`Self::from_branch`], for logics whose nodes wrap modal ones or
+    /// have other expressions, whose atoms are given by `interpretation`
+    /// (see [`Node::interpretation`]).
+    pub(crate) fn from_nodes<'a, E: 'a>(
+        nodes: impl IntoIterator<Item = &'a Node<E>>,
+        interpretation: impl Fn(&'a Node<E>) -> Option<(&'a str, bool, World)>,
+    ) -> Self {
+        let mut model = KripkeModel::default();
+        for node in nodes {
+            match node {
+                Node::Expr { world, .. } => {
+                    model.worlds.insert(*world);
+                    model.valuation.entry(*world).or_default();
+                }
+                Node::Relation { from, to } => {
+                    model.worlds.extend([*from, *to]);
+                    model.relation.insert((*from, *to));
+                }
+            }
+
+            if let Some((name, value, world)) = interpretation(node) {
+                model
+                    .valuation
+                    .entry(world)
+                    .or_default()
+                    .insert(Box::from(name), value);
+            }
+        }
+
+        model
+    }
+}
+
+impl fmt::Display for KripkeModel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "W = {{")?;
+        for (i, world) in self.worlds.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{world}")?;
+        }
+        writeln!(f, "}}")?;
+
+        write!(f, "R = {{")?;
+        for (i, (from, to)) in self.relation.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{from}r{to}")?;
+        }
+        writeln!(f, "}}")?;
+
+        for (world, valuation) in &self.valuation {
+            for (name, value) in valuation {
+                writeln!(f, "v_{world}({name}) = {}", u8::from(*value))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Inspect for Expr {
+    fn inspect(&self) -> Term<'_, Self> {
         match self {
-            Self::Const(name) => f.write_str(&name),
-            Self::Not(p) => write!(f, "¬{p}"),
-            Self::And(x, y) => write!(f, "({x} ∧ {y})"),
-            Self::Or(x, y) => write!(f, "({x} ∨ {y})"),
-            Self::MatImpl(x, y) => write!(f, "({x} ⊃ {y})"),
-            Self::MatEquiv(x, y) => write!(f, "({x} ≡ {y})"),
-            Self::Possibility(p) => write!(f, "⋄{p}"),
-            Self::Necessity(p) => write!(f, "□{p}"),
+            Self::Const(name) => Term::Constant(name),
+            Self::Not(p) => Term::Unary(Symbol::Not, p),
+            Self::And(x, y) => Term::Binary(Symbol::And, x, y),
+            Self::Or(x, y) => Term::Binary(Symbol::Or, x, y),
+            Self::MatImpl(x, y) => Term::Binary(Symbol::MatImpl, x, y),
+            Self::MatEquiv(x, y) => Term::Binary(Symbol::MatEquiv, x, y),
+            Self::Possibility(p) => Term::Unary(Symbol::Possib, p),
+            Self::Necessity(p) => Term::Unary(Symbol::Necess, p),
         }
     }
 }
 
+impl fmt::Display for Expr {
+    /// Prints the expression in unicode notation, see [`Printer`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
 impl fmt::Display for World {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
-impl fmt::Display for Node {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl<E: Print> Print for Node<E> {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
         match self {
-            Node::Expr { expr, world } => write!(f, "{expr}, {world}"),
+            Node::Expr { expr, world } => {
+                printer.write(f, expr)?;
+                write!(f, ", {world}")
+            }
             Node::Relation { from, to } => write!(f, "{from}r{to}"),
         }
     }
 }
 
+impl<E: Print> fmt::Display for Node<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
 #[cfg(feature = "parse")]
 impl FromStr for Expr {
-    // TODO: It would be really nice to just return the proper error :/
-    type Err = String;
+    type Err = ParseError;
 
+    /// Parses an expression with the default [`Grammar`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::modal::Expr;
+    ///
+    /// // Modal operators bind as tightly as `¬`.
+    /// assert_eq!("□p ∧ ◇q ⊃ r".parse::<Expr>(), "((□p) ∧ (◇q)) ⊃ r".parse());
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use crate::logic::lexer::*;
-        use winnow::{
-            ModalResult, Parser,
-            ascii::space0,
-            combinator::alt,
-            combinator::{delimited, preceded},
-            seq,
-        };
+        Self::parse_with(s, &Grammar::new())
+    }
+}
 
-        fn expr_single<'a>(input: &mut &'a str) -> ModalResult<Expr> {
-            let main = alt((
-                delimited('(', expr, ')'),
-                ident.map(|name: &str| Expr::Const(name.to_string().into_boxed_str())),
-                preceded(Symbol::Not.parser(), expr_single).map(|expr| Expr::Not(Box::new(expr))),
-                preceded(Symbol::Possib.parser(), expr_single)
-                    .map(|p| Expr::Possibility(Box::new(p))),
-                preceded(Symbol::Necess.parser(), expr_single)
-                    .map(|p| Expr::Necessity(Box::new(p))),
-            ));
-
-            delimited(space0, main, space0).parse_next(input)
-        }
+#[cfg(feature = "parse")]
+impl Expr {
+    /// Parses an expression, grouping chained connectives as given by
+    /// `grammar`.
+    pub fn parse_with(s: &str, grammar: &Grammar) -> Result<Self, ParseError> {
+        crate::logic::parse(s, Modal::symbols(), grammar)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Construct for Expr {
+    fn constant(name: &str) -> Self {
+        Expr::Const(name.into())
+    }
 
-        fn expr<'a>(input: &mut &'a str) -> ModalResult<Expr> {
-            let main = alt((
-                seq!(expr_single, Symbol::And.parser(), expr)
-                    .map(|(a, _, b)| Expr::And(Box::new(a), Box::new(b))),
-                seq!(expr_single, Symbol::Or.parser(), expr)
-                    .map(|(a, _, b)| Expr::Or(Box::new(a), Box::new(b))),
-                seq!(expr_single, Symbol::MatImpl.parser(), expr)
-                    .map(|(a, _, b)| Expr::MatImpl(Box::new(a), Box::new(b))),
-                seq!(expr_single, Symbol::MatEquiv.parser(), expr)
-                    .map(|(a, _, b)| Expr::MatEquiv(Box::new(a), Box::new(b))),
-                expr_single,
-            ));
-
-            delimited(space0, main, space0).parse_next(input)
+    fn unary(symbol: Symbol, operand: Self) -> Self {
+        let operand = Box::new(operand);
+        match symbol {
+            Symbol::Not => Expr::Not(operand),
+            Symbol::Possib => Expr::Possibility(operand),
+            Symbol::Necess => Expr::Necessity(operand),
+            _ => unreachable!("{symbol:?} isn't a modal operator"),
         }
+    }
 
-        // TODO: It would be really nice to just return the proper error :/
-        expr.parse(s).map_err(|err| err.to_string())
+    fn binary(symbol: Symbol, lhs: Self, rhs: Self) -> Self {
+        let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+        match symbol {
+            Symbol::And => Expr::And(lhs, rhs),
+            Symbol::Or => Expr::Or(lhs, rhs),
+            Symbol::MatImpl => Expr::MatImpl(lhs, rhs),
+            Symbol::MatEquiv => Expr::MatEquiv(lhs, rhs),
+            _ => unreachable!("{symbol:?} isn't a modal connective"),
+        }
     }
 }
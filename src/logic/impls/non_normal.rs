@@ -0,0 +1,503 @@
+use std::{borrow::Cow, collections::BTreeSet, fmt};
+
+use crate::{
+    Logic, PartialTableau,
+    logic::{
+        InferenceRule, Model, Notation, Place, Print, Printer, Propositional, Symbol,
+        impls::worlds::LoopCheck,
+        modal::{self, Expr, KripkeModel, Modal, World},
+        normal_modal::NormalModal,
+    },
+    tableau::{Branch, NodeId},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The non-normal modal logics of Priest's chapter 4, where `□A` is false and
+/// `◇A` is true at non-normal worlds, whatever they access.
+///
+/// Without conditions this is N, with ρ it's S2 and with ρτ it's S3.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::{PartialTableau, logic::non_normal::{self, NonNormal}};
+///
+/// // Necessitation fails, since the worlds that 0 accesses might not be
+/// // normal.
+/// let tableau = "⊢ □(p ∨ ¬p)".parse::<PartialTableau<NonNormal>>().unwrap();
+/// assert!(tableau.infer().holds());
+/// let tableau = "⊢ □□(p ∨ ¬p)".parse::<PartialTableau<NonNormal>>().unwrap();
+/// assert!(!tableau.infer().holds());
+///
+/// let argument = "□(p ⊃ q) ⊢ □(□p ⊃ □q)";
+/// let tableau = PartialTableau::parse_with(non_normal::S2, argument, str::parse).unwrap();
+/// assert!(!tableau.infer().holds());
+/// let tableau = PartialTableau::parse_with(non_normal::S3, argument, str::parse).unwrap();
+/// assert!(tableau.infer().holds());
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonNormal {
+    /// ρ, for every world w, `w R w`
+    pub reflexive: bool,
+    /// τ, if `w1 R w2` and `w2 R w3,` then `w1 R w3`
+    pub transitive: bool,
+}
+
+/// N
+pub const N: NonNormal = NonNormal::new();
+
+/// Nρ
+pub const S2: NonNormal = NonNormal::new().reflexive();
+
+/// Nρτ
+pub const S3: NonNormal = NonNormal::new().reflexive().transitive();
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Node {
+    /// A formula at a world or a relation between worlds, as in
+    /// [modal logic](modal).
+    Modal(modal::Node),
+    /// The world is normal.
+    ///
+    /// World 0 is, since validity is about normal worlds, and so is every
+    /// world where some `□A` holds. Any other world is taken not to be, which
+    /// only makes more formulas true there.
+    Normal(World),
+}
+
+/// A [`KripkeModel`] where only some of the worlds are normal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NonNormalModel {
+    pub kripke: KripkeModel,
+    /// The normal worlds. At every other world, `□A` is false and `◇A` is
+    /// true.
+    pub normal: BTreeSet<World>,
+}
+
+impl Logic for NonNormal {
+    type Node = Node;
+    type Expr = Expr;
+    type Countermodel = NonNormalModel;
+
+    fn symbol(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match (self.reflexive, self.transitive) {
+            (false, false) => "N",
+            (true, false) => "Nρ",
+            (false, true) => "Nτ",
+            (true, true) => "Nρτ",
+        })
+    }
+
+    fn infer(&self, node: &Self::Node, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        use InferenceRule as IR;
+
+        let Node::Modal(node) = node else {
+            return IR::none();
+        };
+
+        match node {
+            modal::Node::Expr { expr, world } => {
+                let world = *world;
+                if let Some(inference) = expr.propositional_inference() {
+                    return inference.map(|expr| Node::Modal(modal::Node::Expr { expr, world }));
+                }
+
+                match expr {
+                    Expr::Possibility(p) => {
+                        // Non-normal worlds make `◇A` true without a witness.
+                        if !branch.contains(&Node::Normal(world)) {
+                            return IR::none();
+                        }
+
+                        if self.transitive {
+                            let witnessed = successors(&branch, world).any(|other_world| {
+                                branch.contains(&Node::Modal(modal::Node::Expr {
+                                    expr: *p.clone(),
+                                    world: other_world,
+                                }))
+                            });
+
+                            // A blocked world reuses the worlds accessible
+                            // from the world that blocks it, see
+                            // `Self::countermodel`.
+                            if witnessed || self.blocker(&branch, world).is_some() {
+                                return IR::none();
+                            }
+                        }
+
+                        let fresh_world = branch.fresh_world();
+                        let basic = [
+                            modal::Node::Relation {
+                                from: world,
+                                to: fresh_world,
+                            },
+                            modal::Node::Expr {
+                                expr: *p.clone(),
+                                world: fresh_world,
+                            },
+                        ]
+                        .into_iter();
+                        let r = self.reflexive.then_some(modal::Node::Relation {
+                            from: fresh_world,
+                            to: fresh_world,
+                        });
+
+                        IR::chain(basic.chain(r).map(Node::Modal).collect())
+                    }
+                    Expr::Necessity(p) => {
+                        // `□A` is false at non-normal worlds, so the world
+                        // has to be normal.
+                        let accessible = successors(&branch, world)
+                            .map(|other_world| {
+                                Node::Modal(modal::Node::Expr {
+                                    expr: *p.clone(),
+                                    world: other_world,
+                                })
+                            })
+                            .collect::<Vec<_>>();
+
+                        IR::chain(
+                            [Node::Normal(world)]
+                                .into_iter()
+                                .chain(accessible)
+                                .collect(),
+                        )
+                    }
+                    _ => unreachable!("{expr} has a propositional rule"),
+                }
+            }
+            modal::Node::Relation { from: j, to: k } => {
+                if !self.transitive {
+                    return IR::none();
+                }
+
+                // We have j->k, we get the i->j and add i->k
+                IR::chain(
+                    branch
+                        .relations_to(*j)
+                        .filter_map(|other| match other {
+                            Node::Modal(modal::Node::Relation { from: i, .. }) => {
+                                Some(Node::Modal(modal::Node::Relation { from: *i, to: *k }))
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        let Node::Modal(leaf) = branch.leaf() else {
+            return None;
+        };
+
+        branch.position(&Node::Modal(leaf.complement()?))
+    }
+
+    fn rule_name(
+        &self,
+        node: &Self::Node,
+        _inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        match node {
+            Node::Modal(modal::Node::Expr { expr, .. }) => Cow::Borrowed(expr.rule_name()),
+            Node::Modal(modal::Node::Relation { .. }) => Cow::Borrowed("τ"),
+            Node::Normal(_) => Cow::Borrowed(""),
+        }
+    }
+
+    fn countermodel(&self, branch: impl Branch<Self>) -> NonNormalModel {
+        let normal = branch
+            .ancestors()
+            .filter_map(|node| match node {
+                Node::Normal(world) => Some(*world),
+                Node::Modal(_) => None,
+            })
+            .collect::<BTreeSet<_>>();
+
+        // Normal worlds that were blocked by loop checking access the same
+        // worlds as the world that blocks them.
+        let loop_check = self.loop_checker();
+        let blocked = normal
+            .iter()
+            .filter_map(|&world| Some((world, self.blocker(&branch, world)?)))
+            .filter(|&(world, _)| {
+                let label = loop_check.label(&branch, world);
+                label.iter().any(|expr| match expr {
+                    Expr::Possibility(p) => !successors(&branch, world).any(|successor| {
+                        branch.contains(&Node::Modal(modal::Node::Expr {
+                            expr: *p.clone(),
+                            world: successor,
+                        }))
+                    }),
+                    _ => false,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut kripke =
+            KripkeModel::from_nodes(branch.ancestors().filter_map(Node::modal), |node| {
+                node.interpretation()
+            });
+        for (world, blocker) in blocked {
+            let successors = kripke.accessible_from(blocker).collect::<Vec<_>>();
+            kripke
+                .relation
+                .extend(successors.into_iter().map(|successor| (world, successor)));
+        }
+
+        kripke.close_frame(&NormalModal {
+            reflexive: self.reflexive,
+            transitive: self.transitive,
+            ..NormalModal::new()
+        });
+
+        NonNormalModel { kripke, normal }
+    }
+
+    fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
+        Node::Modal(Modal {}.make_premise_node(expr))
+    }
+
+    fn make_conclusion_node(&self, expr: Self::Expr) -> Self::Node {
+        Node::Modal(Modal {}.make_conclusion_node(expr))
+    }
+
+    fn initialize(tableau: &mut PartialTableau<Self>) {
+        for leaf in tableau.live_leaves() {
+            let leaf = tableau
+                .add_child(leaf, Node::Normal(World::ZERO))
+                .unwrap_or(leaf);
+
+            if tableau.logic.reflexive {
+                tableau.add_child(
+                    leaf,
+                    Node::Modal(modal::Node::Relation {
+                        from: World::ZERO,
+                        to: World::ZERO,
+                    }),
+                );
+            }
+        }
+    }
+
+    fn reinfers(&self) -> bool {
+        true
+    }
+
+    fn reinfer_on(&self, node: &Self::Node, new_node: &Self::Node) -> bool {
+        match (node, new_node) {
+            // `◇A` only needs a witness once its world turns out to be normal.
+            (
+                Node::Modal(modal::Node::Expr {
+                    expr: Expr::Possibility(_),
+                    world,
+                }),
+                Node::Normal(new_world),
+            ) => world == new_world,
+            // A blocked world might stop being blocked when it gets new
+            // formulas or relations.
+            (
+                Node::Modal(modal::Node::Expr {
+                    expr: Expr::Possibility(_),
+                    world,
+                }),
+                Node::Modal(
+                    modal::Node::Expr {
+                        world: new_world, ..
+                    }
+                    | modal::Node::Relation { to: new_world, .. },
+                ),
+            ) => self.transitive && world == new_world,
+            // We have `j r k` and a new `i r j`, so we need `i r k`.
+            (
+                Node::Modal(modal::Node::Relation { from: j, .. }),
+                Node::Modal(modal::Node::Relation { to: j_new, .. }),
+            ) => self.transitive && j == j_new,
+            (Node::Modal(node), Node::Modal(new_node)) => Modal {}.reinfer_on(node, new_node),
+            _ => false,
+        }
+    }
+
+    fn worlds_in(&self, node: &Self::Node) -> usize {
+        match node {
+            Node::Modal(node) => node.worlds_in(),
+            // The world already appears in the formula that made it normal.
+            Node::Normal(_) => 0,
+        }
+    }
+
+    fn place(&self, node: &Self::Node) -> Option<Place> {
+        Some(node.modal()?.place())
+    }
+
+    fn priority(&self, node: &Self::Node) -> u16 {
+        match node {
+            Node::Modal(node) => match node {
+                // With loop checking, worlds should be as complete as possible
+                // before deciding whether they are blocked.
+                modal::Node::Expr { expr, .. } if self.transitive => match expr {
+                    Expr::Possibility(_) => 0,
+                    Expr::Necessity(_) => 1,
+                    _ => Modal {}.priority(node),
+                },
+                _ => Modal {}.priority(node),
+            },
+            Node::Normal(_) => 100,
+        }
+    }
+}
+
+impl NonNormal {
+    /// Creates N, the basic non-normal modal logic.
+    pub const fn new() -> Self {
+        Self {
+            reflexive: false,
+            transitive: false,
+        }
+    }
+
+    pub const fn reflexive(self) -> Self {
+        Self {
+            reflexive: true,
+            ..self
+        }
+    }
+
+    pub const fn transitive(self) -> Self {
+        Self {
+            transitive: true,
+            ..self
+        }
+    }
+
+    fn loop_checker(&self) -> LoopCheck<Self> {
+        LoopCheck {
+            modal: Node::modal,
+            transitive: self.transitive,
+            exact: false,
+        }
+    }
+
+    /// The earliest normal world that blocks the given world, if any, see
+    /// [`LoopCheck::blocker`].
+    ///
+    /// Only needed with τ, since tableaux are finite otherwise.
+    fn blocker(&self, branch: &impl Branch<Self>, world: World) -> Option<World> {
+        if !self.transitive {
+            return None;
+        }
+
+        self.loop_checker()
+            .blocker(branch, world, |other| branch.contains(&Node::Normal(other)))
+    }
+
+    /// Symbols used in non-normal modal logic.
+    pub const fn symbols() -> &'static [Symbol] {
+        Modal::symbols()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl NonNormal {
+    /// Symbols used in non-normal modal logic.
+    #[wasm_bindgen(js_name = symbols)]
+    pub fn symbols_wasm() -> Vec<Symbol> {
+        Self::symbols().to_vec()
+    }
+}
+
+impl Node {
+    pub fn modal(&self) -> Option<&modal::Node> {
+        match self {
+            Self::Modal(node) => Some(node),
+            Self::Normal(_) => None,
+        }
+    }
+}
+
+/// The worlds that `world` accesses in the branch.
+fn successors<'b>(
+    branch: &'b impl Branch<NonNormal>,
+    world: World,
+) -> impl Iterator<Item = World> + 'b {
+    branch
+        .relations_from(world)
+        .filter_map(move |relation| relation.modal()?.accessible_world_from(world))
+}
+
+impl Model for NonNormalModel {
+    type Expr = Expr;
+    type World = World;
+
+    /// Whether `expr` is true at `world`, where `□A` and `◇A` follow the
+    /// relation only at normal worlds.
+    ///
+    /// Atoms without a value at `world` are taken to be false.
+    fn evaluate(&self, expr: &Expr, world: World) -> bool {
+        let normal = self.normal.contains(&world);
+        match expr {
+            Expr::Const(_) => self.kripke.evaluate(expr, world),
+            Expr::Not(p) => !self.evaluate(p, world),
+            Expr::And(p, q) => self.evaluate(p, world) && self.evaluate(q, world),
+            Expr::Or(p, q) => self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatImpl(p, q) => !self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatEquiv(p, q) => self.evaluate(p, world) == self.evaluate(q, world),
+            Expr::Possibility(p) => {
+                !normal
+                    || self
+                        .kripke
+                        .accessible_from(world)
+                        .any(|other| self.evaluate(p, other))
+            }
+            Expr::Necessity(p) => {
+                normal
+                    && self
+                        .kripke
+                        .accessible_from(world)
+                        .all(|other| self.evaluate(p, other))
+            }
+        }
+    }
+}
+
+impl Print for Node {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Node::Modal(node) => node.write_with(printer, f),
+            Node::Normal(world) => match printer.notation {
+                Notation::Unicode => write!(f, "{world} ∈ N"),
+                Notation::Ascii => write!(f, "{world} in N"),
+                Notation::Latex => write!(f, "{world} \\in N"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+impl fmt::Display for NonNormalModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "N = {{")?;
+        for (i, world) in self.normal.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{world}")?;
+        }
+        writeln!(f, "}}")?;
+
+        self.kripke.fmt(f)
+    }
+}
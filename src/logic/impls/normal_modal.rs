@@ -3,11 +3,12 @@ use std::{borrow::Cow, cmp, collections::HashSet};
 use crate::{
     Logic, PartialTableau,
     logic::{
-        InferenceRule,
+        InferenceRule, Place, Propositional,
+        impls::worlds::LoopCheck,
         lexer::Symbol,
-        modal::{Expr, Modal, Node, World},
+        modal::{Expr, KripkeModel, Modal, Node, World},
     },
-    tableau::Branch,
+    tableau::{Branch, NodeId},
 };
 
 #[cfg(feature = "wasm")]
@@ -15,6 +16,7 @@ use wasm_bindgen::prelude::*;
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NormalModal {
     /// ρ, for every world w, `w R w`
     pub reflexive: bool,
@@ -22,13 +24,14 @@ pub struct NormalModal {
     pub symmetric: bool,
     /// τ, if `w1 R w2` and `w2 R w3,` then `w1 R w3`
     pub transitive: bool,
-    /// η, if `w1`
+    /// η, for every world `w1` there is some `w2` such that `w1 R w2`
     pub extendable: bool,
 }
 
 impl Logic for NormalModal {
     type Node = Node;
     type Expr = Expr;
+    type Countermodel = KripkeModel;
 
     fn symbol(&self) -> Cow<'static, str> {
         let mut output = String::with_capacity(5);
@@ -52,36 +55,35 @@ impl Logic for NormalModal {
     fn infer(&self, node: &Self::Node, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
         use InferenceRule as IR;
 
-        let inferrence = match node {
+        match node {
             Node::Expr { expr, world } => {
                 let world = *world;
+                if let Some(inference) = expr.propositional_inference() {
+                    return inference.map(|expr| Node::Expr { expr, world });
+                }
 
-                let classical_inference: IR<Expr> = match expr {
-                    Expr::Const(_) => IR::none(),
-                    Expr::Not(p) => match p.as_ref() {
-                        Expr::Const(_) => IR::none(),
-                        Expr::Not(p) => IR::single(*p.clone()),
-                        Expr::And(p, q) => IR::split(p.not(), q.not()),
-                        Expr::Or(p, q) => IR::chain(vec![p.not(), q.not()]),
-                        Expr::MatImpl(p, q) => IR::chain(vec![*p.clone(), q.not()]),
-                        Expr::MatEquiv(p, q) => {
-                            IR::split_and_chain([p.not(), *q.clone()], [*p.clone(), q.not()])
-                        }
-                        Expr::Possibility(p) => IR::Single(Expr::Necessity(Box::new(p.not()))),
-                        Expr::Necessity(p) => IR::Single(Expr::Possibility(Box::new(p.not()))),
-                    },
-                    Expr::And(p, q) => IR::chain(vec![*p.clone(), *q.clone()]),
-                    Expr::Or(p, q) => IR::split(*p.clone(), *q.clone()),
-                    Expr::MatImpl(p, q) => IR::split(p.not(), *q.clone()),
-                    Expr::MatEquiv(p, q) => {
-                        IR::split_and_chain([*p.clone(), *q.clone()], [p.not(), q.not()])
-                    }
+                match expr {
                     Expr::Possibility(p) => {
-                        let max_so_far = branch
-                            .ancestors()
-                            .filter_map(|ancestor| ancestor.world())
-                            .max();
-                        let fresh_world = max_so_far.map_or(World::ZERO, |i| i.next());
+                        if self.loop_check() {
+                            let witnessed = branch
+                                .relations_from(world)
+                                .filter_map(|relation| relation.accessible_world_from(world))
+                                .any(|other_world| {
+                                    branch.contains(&Node::Expr {
+                                        expr: *p.clone(),
+                                        world: other_world,
+                                    })
+                                });
+
+                            // A blocked world reuses the worlds accessible
+                            // from the world that blocks it, see
+                            // `Self::countermodel`.
+                            if witnessed || self.blocker(&branch, world).is_some() {
+                                return IR::chain(Vec::new());
+                            }
+                        }
+
+                        let fresh_world = branch.fresh_world();
 
                         // These nodes always get added
                         let basic = [
@@ -97,33 +99,30 @@ impl Logic for NormalModal {
                         .into_iter();
 
                         // Reflexive relation, create `i r i` for every new world `i`
-                        let r = self.reflexive.then(|| Node::Relation {
+                        let r = self.reflexive.then_some(Node::Relation {
                             from: fresh_world,
                             to: fresh_world,
                         });
 
-                        return IR::chain(basic.chain(r).collect());
+                        IR::chain(basic.chain(r).collect())
                     }
-                    Expr::Necessity(p) => {
-                        return IR::chain(
-                            branch
-                                .ancestors()
-                                .filter_map(|ancestor| ancestor.accessible_world_from(world))
-                                .map(|other_world| Node::Expr {
-                                    expr: *p.clone(),
-                                    world: other_world,
-                                })
-                                .collect(),
-                        );
-                    }
-                };
-
-                classical_inference.map(|expr| Node::Expr { expr, world })
+                    Expr::Necessity(p) => IR::chain(
+                        branch
+                            .relations_from(world)
+                            .filter_map(|relation| relation.accessible_world_from(world))
+                            .map(|other_world| Node::Expr {
+                                expr: *p.clone(),
+                                world: other_world,
+                            })
+                            .collect(),
+                    ),
+                    _ => unreachable!("{expr} has a propositional rule"),
+                }
             }
             Node::Relation { from, to } => {
                 let s = self
                     .symmetric
-                    .then(|| Node::Relation {
+                    .then_some(Node::Relation {
                         from: *to,
                         to: *from,
                     })
@@ -137,13 +136,10 @@ impl Logic for NormalModal {
                         let j = from;
                         let k = to;
                         branch
-                            .ancestors()
-                            .filter_map(move |other| match other {
-                                Node::Relation {
-                                    from: i,
-                                    to: j_other,
-                                } if j == j_other => Some(i),
-                                _ => None,
+                            .relations_to(*j)
+                            .filter_map(|other| match other {
+                                Node::Relation { from: i, .. } => Some(i),
+                                Node::Expr { .. } => None,
                             })
                             .map(|i| Node::Relation { from: *i, to: *k })
                     })
@@ -152,56 +148,108 @@ impl Logic for NormalModal {
 
                 IR::chain(t.chain(s).collect())
             }
+        }
+    }
+
+    fn saturate(&self, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        // Every world already accesses itself in reflexive logics.
+        if !self.extendable || self.reflexive {
+            return InferenceRule::none();
+        }
+
+        // Add a new relation i->j to a fresh j from an existing i that doesn't
+        // access any world yet.
+        let fresh_world = branch.fresh_world();
+        let Some(leaf_world) = (0..fresh_world.index()).map(World::new).find(|world| {
+            branch.relations_from(*world).next().is_none()
+                && self.blocker(&branch, *world).is_none()
+        }) else {
+            return InferenceRule::none();
         };
 
-        // Add a new relation i->j to a fresh j from an existing i
-        if matches!(inferrence, IR::None) && self.extendable {
-            // Worlds that don't access any new worlds
-            let mut maybe_leaf_worlds = HashSet::new();
-            let mut non_leaf_worlds = HashSet::new();
-            for node in branch.ancestors() {
-                match node {
-                    Node::Expr { world, .. } => {
-                        maybe_leaf_worlds.insert(*world);
-                    }
-                    Node::Relation { from: i, to: j } => {
-                        non_leaf_worlds.insert(*i);
-                        maybe_leaf_worlds.insert(*j);
-                    }
-                }
-            }
+        InferenceRule::single(Node::Relation {
+            from: leaf_world,
+            to: fresh_world,
+        })
+    }
 
-            let mut leaf_worlds = maybe_leaf_worlds.difference(&non_leaf_worlds);
-            let Some(leaf_world) = leaf_worlds.next() else {
-                return IR::none();
-            };
-
-            let max_so_far = branch
-                .ancestors()
-                .filter_map(|ancestor| ancestor.world())
-                .max();
-            let fresh_world = max_so_far.map_or(World::ZERO, |i| i.next());
-
-            return IR::single(Node::Relation {
-                from: *leaf_world,
-                to: fresh_world,
-            });
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        branch.position(&branch.leaf().complement()?)
+    }
+
+    fn rule_name(
+        &self,
+        node: &Self::Node,
+        inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        // Extending to a fresh world is the only rule that yields a single
+        // relation.
+        if let InferenceRule::Single(Node::Relation { .. }) = inference {
+            return Cow::Borrowed("η");
         }
 
-        inferrence
-    }
+        match node {
+            Node::Expr { expr, .. } => Cow::Borrowed(expr.rule_name()),
+            Node::Relation { from, to } => {
+                let InferenceRule::Chain(relations) = inference else {
+                    return Cow::Borrowed("");
+                };
 
-    fn has_contradiction(&self, branch: impl Branch<Self>) -> bool {
-        let Some((name, value, world)) = branch.leaf().interpretation() else {
-            return false;
-        };
+                let symmetric = relations
+                    .iter()
+                    .any(|relation| relation.accessible_world_from(*to) == Some(*from));
+                let transitive = relations
+                    .iter()
+                    .any(|relation| relation.accessible_world_from(*to) != Some(*from));
+
+                Cow::Borrowed(match (symmetric, transitive) {
+                    (true, true) => "στ",
+                    (true, false) => "σ",
+                    (false, true) => "τ",
+                    (false, false) => "",
+                })
+            }
+        }
+    }
 
-        branch
-            .ancestors()
-            .filter_map(|ancestor| ancestor.interpretation())
-            .any(|(other_name, other_value, other_world)| {
-                other_name == name && other_world == world && other_value != value
+    fn countermodel(&self, branch: impl Branch<Self>) -> KripkeModel {
+        // Worlds that were blocked by loop checking access the same worlds
+        // as the world that blocks them.
+        let loop_check = self.loop_checker();
+        let blocked = (0..branch.fresh_world().index())
+            .map(World::new)
+            .filter_map(|world| Some((world, self.blocker(&branch, world)?)))
+            .filter(|&(world, _)| {
+                let successors = branch
+                    .relations_from(world)
+                    .filter_map(|relation| relation.accessible_world_from(world))
+                    .collect::<Vec<_>>();
+
+                let label = loop_check.label(&branch, world);
+                let unwitnessed = label.iter().any(|expr| match expr {
+                    Expr::Possibility(p) => !successors.iter().any(|successor| {
+                        branch.contains(&Node::Expr {
+                            expr: *p.clone(),
+                            world: *successor,
+                        })
+                    }),
+                    _ => false,
+                });
+
+                unwitnessed || (self.extendable && successors.is_empty())
             })
+            .collect::<Vec<_>>();
+
+        let mut model = KripkeModel::from_branch(branch);
+        for (world, blocker) in blocked {
+            let successors = model.accessible_from(blocker).collect::<Vec<_>>();
+            model
+                .relation
+                .extend(successors.into_iter().map(|successor| (world, successor)));
+        }
+
+        model.close_frame(self);
+        model
     }
 
     fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
@@ -244,8 +292,51 @@ impl Logic for NormalModal {
         }
     }
 
+    fn reinfers(&self) -> bool {
+        true
+    }
+
+    fn reinfer_on(&self, node: &Self::Node, new_node: &Self::Node) -> bool {
+        match (node, new_node) {
+            // A blocked world might stop being blocked when it gets new
+            // formulas or relations.
+            (
+                Node::Expr {
+                    expr: Expr::Possibility(_),
+                    world,
+                },
+                Node::Expr {
+                    world: new_world, ..
+                }
+                | Node::Relation { to: new_world, .. },
+            ) => self.loop_check() && world == new_world,
+            // We have `j r k` and a new `i r j`, so we need `i r k`.
+            (Node::Relation { from: j, .. }, Node::Relation { to: j_new, .. }) => {
+                self.transitive && j == j_new
+            }
+            _ => Modal {}.reinfer_on(node, new_node),
+        }
+    }
+
+    fn worlds_in(&self, node: &Self::Node) -> usize {
+        node.worlds_in()
+    }
+
+    fn place(&self, node: &Self::Node) -> Option<Place> {
+        Some(node.place())
+    }
+
     fn priority(&self, node: &Self::Node) -> u16 {
-        Modal {}.priority(node)
+        match node {
+            // With loop checking, worlds should be as complete as possible
+            // before deciding whether they are blocked.
+            Node::Expr { expr, .. } if self.loop_check() => match expr {
+                Expr::Possibility(_) => 0,
+                Expr::Necessity(_) => 1,
+                _ => Modal {}.priority(node),
+            },
+            _ => Modal {}.priority(node),
+        }
     }
 }
 
@@ -292,6 +383,51 @@ impl NormalModal {
         }
     }
 
+    /// Whether the tableau might be infinite without loop checking, which
+    /// happens with transitivity or extendability.
+    ///
+    /// With loop checking, tableaux always end up either closed or with
+    /// finite countermodels.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use tableaux::logic::{Logic, Model, modal::{Expr, World}, normal_modal};
+    ///
+    /// let premises = [Expr::from_str("□◇p").unwrap()];
+    /// let conclusion = Expr::from_str("◇□p").unwrap();
+    /// let tableau = normal_modal::S4.tableau(premises.clone(), conclusion.clone()).infer();
+    ///
+    /// assert!(!tableau.holds());
+    /// for countermodel in tableau.countermodels() {
+    ///     assert!(countermodel.refutes(&premises, &conclusion, World::ZERO));
+    ///     assert_eq!(countermodel.check_frame(&normal_modal::S4), Ok(()));
+    /// }
+    /// ```
+    pub const fn loop_check(&self) -> bool {
+        self.transitive || self.extendable
+    }
+
+    fn loop_checker(&self) -> LoopCheck<Self> {
+        LoopCheck {
+            modal: |node| Some(node),
+            transitive: self.transitive,
+            exact: self.symmetric,
+        }
+    }
+
+    /// The earliest world that blocks the given world, if any, see
+    /// [`LoopCheck::blocker`]. In symmetric logics, the earlier world needs
+    /// exactly the same formulas.
+    fn blocker(&self, branch: &impl Branch<Self>, world: World) -> Option<World> {
+        if !self.loop_check() {
+            return None;
+        }
+
+        self.loop_checker().blocker(branch, world, |_| true)
+    }
+
     const fn normalized(self) -> Self {
         Self {
             // sigma tau eta imply rho
@@ -316,28 +452,13 @@ pub const D: NormalModal = NormalModal {
 };
 
 /// Kρσ
-pub const B: NormalModal = NormalModal {
-    reflexive: false,
-    symmetric: false,
-    transitive: true,
-    extendable: true,
-};
+pub const B: NormalModal = NormalModal::new().reflexive().symmetric();
 
 /// Kρτ
-pub const S4: NormalModal = NormalModal {
-    reflexive: false,
-    symmetric: false,
-    transitive: true,
-    extendable: true,
-};
+pub const S4: NormalModal = NormalModal::new().reflexive().transitive();
 
 /// Kρστ
-pub const S5: NormalModal = NormalModal {
-    reflexive: false,
-    symmetric: false,
-    transitive: true,
-    extendable: true,
-};
+pub const S5: NormalModal = NormalModal::new().reflexive().symmetric().transitive();
 
 impl cmp::PartialEq for NormalModal {
     fn eq(&self, other: &Self) -> bool {
@@ -0,0 +1,744 @@
+use std::{borrow::Cow, collections::BTreeSet, fmt, str::FromStr};
+
+use crate::{
+    Logic, PartialTableau,
+    logic::{
+        Construct, Grammar, InferenceRule, Inspect, Model, ParseError, Place, Print, Printer,
+        Propositional, Symbol, Term,
+        modal::{self, KripkeModel, World},
+    },
+    tableau::{Branch, NodeId, Tableau},
+};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// The tense logic Kt of Priest's 3.6 and its extensions, where the worlds
+/// are times and `w1 R w2` means that `w1` is earlier than `w2`.
+///
+/// `[F]A` and `⟨F⟩A` are about the times later than the current one, like
+/// `□A` and `◇A`, while `[P]A` and `⟨P⟩A` are about the earlier ones, so
+/// they follow the relation backwards.
+///
+/// Convergence and linearity compare two times, which might turn out to be
+/// the same one (see [`Node::Identity`]). With density or linearity, the
+/// tableaux of some arguments that don't hold are infinite, so they have to
+/// be inferred with [`PartialTableau::infer_with_limits`].
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::{PartialTableau, logic::tense::{self, Tense}};
+///
+/// // What is the case will always have been the case.
+/// let tableau = "p ⊢ [F]⟨P⟩p".parse::<PartialTableau<Tense>>().unwrap();
+/// assert!(tableau.infer().holds());
+///
+/// // Two future times are only ordered when time doesn't branch.
+/// let argument = "⟨F⟩p, ⟨F⟩q ⊢ ⟨F⟩(p ∧ ⟨F⟩q) ∨ ⟨F⟩(p ∧ q) ∨ ⟨F⟩(q ∧ ⟨F⟩p)";
+/// let tableau = PartialTableau::parse_with(Tense::new(), argument, str::parse).unwrap();
+/// assert!(!tableau.infer().holds());
+/// let logic = Tense::new().forward_convergent();
+/// let tableau = PartialTableau::parse_with(logic, argument, str::parse).unwrap();
+/// assert!(tableau.infer().holds());
+///
+/// let tableau = PartialTableau::parse_with(tense::DENSE, "⟨F⟩p ⊢ ⟨F⟩⟨F⟩p", str::parse).unwrap();
+/// assert!(tableau.infer().holds());
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tense {
+    /// If `w1 R w2` and `w1 R w3`, then `w2 R w3`, `w2 = w3` or `w3 R w2`
+    pub forward_convergent: bool,
+    /// If `w2 R w1` and `w3 R w1`, then `w2 R w3`, `w2 = w3` or `w3 R w2`
+    pub backward_convergent: bool,
+    /// If `w1 R w2`, then there is some `w3` such that `w1 R w3` and
+    /// `w3 R w2`
+    pub dense: bool,
+    /// For every `w1` and `w2`, `w1 R w2`, `w1 = w2` or `w2 R w1`
+    pub linear: bool,
+}
+
+/// Kt
+pub const KT: Tense = Tense::new();
+
+/// Kt with density
+pub const DENSE: Tense = Tense::new().dense();
+
+/// Kt with linearity
+pub const LINEAR: Tense = Tense::new().linear();
+
+pub fn infer(input: &str) -> Tableau<Tense> {
+    PartialTableau::from_str(input).unwrap().infer()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Node {
+    /// A formula at a world or a relation between worlds, as in
+    /// [modal logic](modal).
+    Modal(modal::Node<Expr>),
+    /// Both worlds are the same time.
+    ///
+    /// The second world is the later one in the tableau, and every node about
+    /// it gets copied to the first one, which stands for both from then on.
+    Identity(World, World),
+}
+
+/// A [`KripkeModel`] of tense logic, where `[P]A` and `⟨P⟩A` follow the
+/// relation backwards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TenseModel {
+    pub kripke: KripkeModel,
+}
+
+impl Logic for Tense {
+    type Node = Node;
+    type Expr = Expr;
+    type Countermodel = TenseModel;
+
+    fn symbol(&self) -> Cow<'static, str> {
+        // Linearity implies both kinds of convergence, as in `PartialEq`.
+        let mut output = String::from("Kₜ");
+        if self.forward_convergent && !self.linear {
+            output.push_str("+FC");
+        }
+        if self.backward_convergent && !self.linear {
+            output.push_str("+BC");
+        }
+        if self.dense {
+            output.push_str("+D");
+        }
+        if self.linear {
+            output.push_str("+L");
+        }
+
+        Cow::Owned(output)
+    }
+
+    fn infer(&self, node: &Self::Node, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        use InferenceRule as IR;
+
+        let merged = merged_worlds(&branch);
+        let node = match node {
+            Node::Identity(world, merged_world) => {
+                return IR::chain(
+                    branch
+                        .ancestors()
+                        .filter_map(|other| other.modal()?.replace(*merged_world, *world))
+                        .map(Node::Modal)
+                        .collect(),
+                );
+            }
+            // The nodes about merged worlds already got copied.
+            Node::Modal(node) if node.worlds().any(|world| merged.contains(&world)) => {
+                return IR::none();
+            }
+            Node::Modal(node) => node,
+        };
+
+        match node {
+            modal::Node::Expr { expr, world } => {
+                let world = *world;
+                if let Some(inference) = expr.propositional_inference() {
+                    return inference.map(|expr| Node::Modal(modal::Node::Expr { expr, world }));
+                }
+
+                let expr_at = |expr: &Expr, world| {
+                    Node::Modal(modal::Node::Expr {
+                        expr: expr.clone(),
+                        world,
+                    })
+                };
+
+                match expr {
+                    Expr::FuturePossibility(p) => {
+                        let fresh_world = branch.fresh_world();
+                        IR::chain(vec![relation(world, fresh_world), expr_at(p, fresh_world)])
+                    }
+                    Expr::PastPossibility(p) => {
+                        let fresh_world = branch.fresh_world();
+                        IR::chain(vec![relation(fresh_world, world), expr_at(p, fresh_world)])
+                    }
+                    Expr::FutureNecessity(p) => IR::chain(
+                        later(&branch, world)
+                            .map(|other_world| expr_at(p, other_world))
+                            .collect(),
+                    ),
+                    Expr::PastNecessity(p) => IR::chain(
+                        earlier(&branch, world)
+                            .map(|other_world| expr_at(p, other_world))
+                            .collect(),
+                    ),
+                    _ => unreachable!("{expr} has a propositional rule"),
+                }
+            }
+            modal::Node::Relation { from, to } => {
+                let (from, to) = (*from, *to);
+
+                // Linearity already compares every pair of times, see
+                // `Self::saturate`.
+                if self.forward_convergent && !self.linear {
+                    let other = later(&branch, from)
+                        .find(|other| !merged.contains(other) && !ordered(&branch, to, *other));
+                    if let Some(other) = other {
+                        return compare(to, other);
+                    }
+                }
+
+                if self.backward_convergent && !self.linear {
+                    let other = earlier(&branch, to)
+                        .find(|other| !merged.contains(other) && !ordered(&branch, from, *other));
+                    if let Some(other) = other {
+                        return compare(from, other);
+                    }
+                }
+
+                let between =
+                    later(&branch, from).any(|other| branch.contains(&relation(other, to)));
+                if self.dense && !between {
+                    let fresh_world = branch.fresh_world();
+                    return IR::chain(vec![relation(from, fresh_world), relation(fresh_world, to)]);
+                }
+
+                IR::none()
+            }
+        }
+    }
+
+    fn saturate(&self, branch: impl Branch<Self>) -> InferenceRule<Self::Node> {
+        if !self.linear {
+            return InferenceRule::none();
+        }
+
+        let merged = merged_worlds(&branch);
+        let worlds = branch
+            .ancestors()
+            .filter_map(Node::modal)
+            .flat_map(modal::Node::worlds)
+            .filter(|world| !merged.contains(world))
+            .collect::<BTreeSet<_>>();
+
+        for &first in &worlds {
+            for &second in worlds.range(first..) {
+                if !ordered(&branch, first, second) {
+                    return compare(first, second);
+                }
+            }
+        }
+
+        InferenceRule::none()
+    }
+
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId> {
+        let Node::Modal(leaf) = branch.leaf() else {
+            return None;
+        };
+
+        branch.position(&Node::Modal(leaf.complement()?))
+    }
+
+    fn rule_name(
+        &self,
+        node: &Self::Node,
+        inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        // Comparing two times is the only rule with three branches.
+        let compared = match &inference {
+            InferenceRule::SplitChains(chains) => {
+                chains.iter().flatten().find_map(|node| match node {
+                    Node::Identity(first, second) => Some([*first, *second]),
+                    Node::Modal(_) => None,
+                })
+            }
+            _ => None,
+        };
+
+        Cow::Borrowed(match (node, compared) {
+            (_, Some(_)) if self.linear => "L",
+            (Node::Modal(modal::Node::Relation { to, .. }), Some(compared)) => {
+                if compared.contains(to) { "FC" } else { "BC" }
+            }
+            (Node::Modal(modal::Node::Relation { .. }), None) => "D",
+            (Node::Modal(modal::Node::Expr { expr, .. }), _) => expr.rule_name(),
+            (Node::Identity(..), _) => "=",
+        })
+    }
+
+    fn countermodel(&self, branch: impl Branch<Self>) -> TenseModel {
+        // Every node about a merged world was copied to the world it's
+        // merged into, so it can just be left out.
+        let merged = merged_worlds(&branch);
+        let nodes = branch
+            .ancestors()
+            .filter_map(Node::modal)
+            .filter(|node| !node.worlds().any(|world| merged.contains(&world)));
+
+        TenseModel {
+            kripke: KripkeModel::from_nodes(nodes, |node| node.interpretation()),
+        }
+    }
+
+    fn make_premise_node(&self, expr: Self::Expr) -> Self::Node {
+        Node::Modal(modal::Node::Expr {
+            expr,
+            world: World::ZERO,
+        })
+    }
+
+    fn make_conclusion_node(&self, expr: Self::Expr) -> Self::Node {
+        Node::Modal(modal::Node::Expr {
+            expr: Expr::Not(Box::new(expr)),
+            world: World::ZERO,
+        })
+    }
+
+    fn reinfers(&self) -> bool {
+        true
+    }
+
+    fn reinfer_on(&self, node: &Self::Node, new_node: &Self::Node) -> bool {
+        let Node::Modal(new_modal) = new_node else {
+            // A new identity might leave two times to compare, since the
+            // merged world no longer counts.
+            return matches!(node, Node::Modal(modal::Node::Relation { .. }))
+                && self.compares_neighbours();
+        };
+
+        match (node, new_modal) {
+            // `[F]A, i` has to be applied to every new `i r j`, and `[P]A, i`
+            // to every new `j r i`.
+            (
+                Node::Modal(modal::Node::Expr {
+                    expr: Expr::FutureNecessity(_),
+                    world,
+                }),
+                modal::Node::Relation { from, .. },
+            ) => world == from,
+            (
+                Node::Modal(modal::Node::Expr {
+                    expr: Expr::PastNecessity(_),
+                    world,
+                }),
+                modal::Node::Relation { to, .. },
+            ) => world == to,
+            (Node::Identity(_, merged_world), new_modal) => {
+                new_modal.worlds().any(|world| world == *merged_world)
+            }
+            // A relation compares its worlds with one other world at a time.
+            (Node::Modal(modal::Node::Relation { .. }), modal::Node::Relation { .. }) => {
+                self.compares_neighbours()
+            }
+            _ => false,
+        }
+    }
+
+    fn worlds_in(&self, node: &Self::Node) -> usize {
+        match node {
+            Node::Modal(node) => node.worlds_in(),
+            // Both worlds already appear in other nodes.
+            Node::Identity(..) => 0,
+        }
+    }
+
+    fn place(&self, node: &Self::Node) -> Option<Place> {
+        Some(node.modal()?.place())
+    }
+
+    fn priority(&self, node: &Self::Node) -> u16 {
+        match node {
+            Node::Modal(modal::Node::Expr { expr, .. }) => match expr {
+                Expr::Const(_) => 5,
+                Expr::Not(_) => 5,
+                Expr::And(_, _) => 7,
+                Expr::Or(_, _) => 5,
+                Expr::MatImpl(_, _) => 5,
+                Expr::MatEquiv(_, _) => 2,
+                Expr::FutureNecessity(_) | Expr::PastNecessity(_) => 1,
+                Expr::FuturePossibility(_) | Expr::PastPossibility(_) => 1000,
+            },
+            // Relations go last, since density adds new ones forever, and
+            // comparing times splits the branch.
+            Node::Modal(modal::Node::Relation { .. }) => 0,
+            Node::Identity(..) => 1000,
+        }
+    }
+}
+
+impl Tense {
+    /// Creates Kt, the basic tense logic.
+    pub const fn new() -> Self {
+        Self {
+            forward_convergent: false,
+            backward_convergent: false,
+            dense: false,
+            linear: false,
+        }
+    }
+
+    pub const fn forward_convergent(self) -> Self {
+        Self {
+            forward_convergent: true,
+            ..self
+        }
+    }
+
+    pub const fn backward_convergent(self) -> Self {
+        Self {
+            backward_convergent: true,
+            ..self
+        }
+    }
+
+    pub const fn dense(self) -> Self {
+        Self {
+            dense: true,
+            ..self
+        }
+    }
+
+    pub const fn linear(self) -> Self {
+        Self {
+            linear: true,
+            ..self
+        }
+    }
+
+    /// Whether relations have to be compared with the relations next to them,
+    /// which linearity does for every pair of times instead.
+    const fn compares_neighbours(&self) -> bool {
+        (self.forward_convergent || self.backward_convergent) && !self.linear
+    }
+
+    const fn normalized(self) -> Self {
+        Self {
+            // Linearity implies both kinds of convergence
+            forward_convergent: self.forward_convergent || self.linear,
+            backward_convergent: self.backward_convergent || self.linear,
+            dense: self.dense,
+            linear: self.linear,
+        }
+    }
+
+    /// Symbols used in tense logic.
+    pub const fn symbols() -> &'static [Symbol] {
+        &[
+            Symbol::Not,
+            Symbol::And,
+            Symbol::Or,
+            Symbol::MatImpl,
+            Symbol::MatEquiv,
+            Symbol::FutureNecess,
+            Symbol::FuturePossib,
+            Symbol::PastNecess,
+            Symbol::PastPossib,
+        ]
+    }
+}
+
+impl PartialEq for Tense {
+    fn eq(&self, other: &Self) -> bool {
+        let a = self.normalized();
+        let b = other.normalized();
+        a.forward_convergent == b.forward_convergent
+            && a.backward_convergent == b.backward_convergent
+            && a.dense == b.dense
+            && a.linear == b.linear
+    }
+}
+
+impl Eq for Tense {}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Tense {
+    /// Symbols used in tense logic.
+    #[wasm_bindgen(js_name = symbols)]
+    pub fn symbols_wasm() -> Vec<Symbol> {
+        Self::symbols().to_vec()
+    }
+}
+
+/// `from r to`.
+fn relation(from: World, to: World) -> Node {
+    Node::Modal(modal::Node::Relation { from, to })
+}
+
+/// Splits the branch into `first r second`, `first = second` and
+/// `second r first`.
+fn compare(first: World, second: World) -> InferenceRule<Node> {
+    InferenceRule::split_chains(vec![
+        vec![relation(first, second)],
+        vec![Node::Identity(first.min(second), first.max(second))],
+        vec![relation(second, first)],
+    ])
+}
+
+/// Whether the branch says how the two times are ordered.
+fn ordered(branch: &impl Branch<Tense>, first: World, second: World) -> bool {
+    first == second
+        || branch.contains(&relation(first, second))
+        || branch.contains(&relation(second, first))
+}
+
+/// The worlds that were merged into others.
+fn merged_worlds(branch: &impl Branch<Tense>) -> BTreeSet<World> {
+    branch
+        .ancestors()
+        .filter_map(|node| match node {
+            Node::Identity(_, merged_world) => Some(*merged_world),
+            Node::Modal(_) => None,
+        })
+        .collect()
+}
+
+/// The worlds later than `world` in the branch.
+fn later<'b>(branch: &'b impl Branch<Tense>, world: World) -> impl Iterator<Item = World> + 'b {
+    branch
+        .relations_from(world)
+        .filter_map(move |relation| relation.modal()?.accessible_world_from(world))
+}
+
+/// The worlds earlier than `world` in the branch.
+fn earlier<'b>(branch: &'b impl Branch<Tense>, world: World) -> impl Iterator<Item = World> + 'b {
+    branch
+        .relations_to(world)
+        .filter_map(|relation| match relation {
+            Node::Modal(modal::Node::Relation { from, .. }) => Some(*from),
+            _ => None,
+        })
+}
+
+impl Node {
+    pub fn modal(&self) -> Option<&modal::Node<Expr>> {
+        match self {
+            Self::Modal(node) => Some(node),
+            Self::Identity(..) => None,
+        }
+    }
+}
+
+impl modal::Node<Expr> {
+    /// The worlds the node is about.
+    fn worlds(&self) -> impl Iterator<Item = World> + use<> {
+        let worlds = match self {
+            Self::Expr { world, .. } => [Some(*world), None],
+            Self::Relation { from, to } => [Some(*from), Some(*to)],
+        };
+
+        worlds.into_iter().flatten()
+    }
+
+    /// The node with `to` in place of `from`, if it's about `from`.
+    fn replace(&self, from: World, to: World) -> Option<Self> {
+        let replace = |world: World| if world == from { to } else { world };
+        match self {
+            Self::Expr { expr, world } if *world == from => Some(Self::Expr {
+                expr: expr.clone(),
+                world: to,
+            }),
+            Self::Relation {
+                from: first,
+                to: second,
+            } if *first == from || *second == from => Some(Self::Relation {
+                from: replace(*first),
+                to: replace(*second),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expr {
+    Const(Box<str>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    MatImpl(Box<Expr>, Box<Expr>),
+    MatEquiv(Box<Expr>, Box<Expr>),
+    /// `[F]A`, it will always be the case that `A`.
+    FutureNecessity(Box<Expr>),
+    /// `⟨F⟩A`, it will at some time be the case that `A`.
+    FuturePossibility(Box<Expr>),
+    /// `[P]A`, it has always been the case that `A`.
+    PastNecessity(Box<Expr>),
+    /// `⟨P⟩A`, it was at some time the case that `A`.
+    PastPossibility(Box<Expr>),
+}
+
+impl Propositional for Expr {
+    fn atom(name: &str) -> Self {
+        Self::Const(name.into())
+    }
+
+    fn not(&self) -> Self {
+        Self::Not(Box::new(self.clone()))
+    }
+
+    /// Negated tense operators just move the negation inwards, such as
+    /// `¬[F]A` to `⟨F⟩¬A`.
+    fn negated(&self) -> Option<Self> {
+        Some(match self {
+            Expr::FutureNecessity(p) => Expr::FuturePossibility(Box::new(p.not())),
+            Expr::FuturePossibility(p) => Expr::FutureNecessity(Box::new(p.not())),
+            Expr::PastNecessity(p) => Expr::PastPossibility(Box::new(p.not())),
+            Expr::PastPossibility(p) => Expr::PastNecessity(Box::new(p.not())),
+            _ => return None,
+        })
+    }
+}
+
+impl Model for TenseModel {
+    type Expr = Expr;
+    type World = World;
+
+    /// Whether `expr` is true at `world`, where `[F]A` and `⟨F⟩A` look at the
+    /// worlds that `world` accesses and `[P]A` and `⟨P⟩A` at the worlds that
+    /// access it.
+    ///
+    /// Atoms without a value at `world` are taken to be false.
+    fn evaluate(&self, expr: &Expr, world: World) -> bool {
+        match expr {
+            Expr::Const(name) => self
+                .kripke
+                .valuation
+                .get(&world)
+                .and_then(|valuation| valuation.get(name))
+                .copied()
+                .unwrap_or(false),
+            Expr::Not(p) => !self.evaluate(p, world),
+            Expr::And(p, q) => self.evaluate(p, world) && self.evaluate(q, world),
+            Expr::Or(p, q) => self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatImpl(p, q) => !self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatEquiv(p, q) => self.evaluate(p, world) == self.evaluate(q, world),
+            Expr::FutureNecessity(p) => self
+                .kripke
+                .accessible_from(world)
+                .all(|other| self.evaluate(p, other)),
+            Expr::FuturePossibility(p) => self
+                .kripke
+                .accessible_from(world)
+                .any(|other| self.evaluate(p, other)),
+            Expr::PastNecessity(p) => self.accessing(world).all(|other| self.evaluate(p, other)),
+            Expr::PastPossibility(p) => self.accessing(world).any(|other| self.evaluate(p, other)),
+        }
+    }
+}
+
+impl TenseModel {
+    /// Iterator over the worlds that can access `world`.
+    fn accessing(&self, world: World) -> impl Iterator<Item = World> + '_ {
+        self.kripke
+            .relation
+            .iter()
+            .filter(move |(_, to)| *to == world)
+            .map(|(from, _)| *from)
+    }
+}
+
+impl Inspect for Expr {
+    fn inspect(&self) -> Term<'_, Self> {
+        match self {
+            Self::Const(name) => Term::Constant(name),
+            Self::Not(p) => Term::Unary(Symbol::Not, p),
+            Self::And(x, y) => Term::Binary(Symbol::And, x, y),
+            Self::Or(x, y) => Term::Binary(Symbol::Or, x, y),
+            Self::MatImpl(x, y) => Term::Binary(Symbol::MatImpl, x, y),
+            Self::MatEquiv(x, y) => Term::Binary(Symbol::MatEquiv, x, y),
+            Self::FutureNecessity(p) => Term::Unary(Symbol::FutureNecess, p),
+            Self::FuturePossibility(p) => Term::Unary(Symbol::FuturePossib, p),
+            Self::PastNecessity(p) => Term::Unary(Symbol::PastNecess, p),
+            Self::PastPossibility(p) => Term::Unary(Symbol::PastPossib, p),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Prints the expression in unicode notation, see [`Printer`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+impl Print for Node {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Node::Modal(node) => node.write_with(printer, f),
+            Node::Identity(first, second) => write!(f, "{first} = {second}"),
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer::new().write(f, self)
+    }
+}
+
+impl fmt::Display for TenseModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kripke.fmt(f)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    /// Parses an expression with the default [`Grammar`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::tense::Expr;
+    ///
+    /// // Tense operators bind as tightly as `¬`, and have ASCII spellings.
+    /// assert_eq!("[F]p ∧ ⟨P⟩q".parse::<Expr>(), "([F]p) ∧ (⟨P⟩q)".parse());
+    /// assert_eq!("<F>p > [P]q".parse::<Expr>(), "⟨F⟩p ⊃ [P]q".parse());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Grammar::new())
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Expr {
+    /// Parses an expression, grouping chained connectives as given by
+    /// `grammar`.
+    pub fn parse_with(s: &str, grammar: &Grammar) -> Result<Self, ParseError> {
+        crate::logic::parse(s, Tense::symbols(), grammar)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Construct for Expr {
+    fn constant(name: &str) -> Self {
+        Expr::Const(name.into())
+    }
+
+    fn unary(symbol: Symbol, operand: Self) -> Self {
+        let operand = Box::new(operand);
+        match symbol {
+            Symbol::Not => Expr::Not(operand),
+            Symbol::FutureNecess => Expr::FutureNecessity(operand),
+            Symbol::FuturePossib => Expr::FuturePossibility(operand),
+            Symbol::PastNecess => Expr::PastNecessity(operand),
+            Symbol::PastPossib => Expr::PastPossibility(operand),
+            _ => unreachable!("{symbol:?} isn't a tense operator"),
+        }
+    }
+
+    fn binary(symbol: Symbol, lhs: Self, rhs: Self) -> Self {
+        let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+        match symbol {
+            Symbol::And => Expr::And(lhs, rhs),
+            Symbol::Or => Expr::Or(lhs, rhs),
+            Symbol::MatImpl => Expr::MatImpl(lhs, rhs),
+            Symbol::MatEquiv => Expr::MatEquiv(lhs, rhs),
+            _ => unreachable!("{symbol:?} isn't a tense connective"),
+        }
+    }
+}
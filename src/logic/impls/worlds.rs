@@ -0,0 +1,87 @@
+//! Helpers shared by the logics whose nodes are labelled with worlds.
+
+use std::collections::HashSet;
+
+use crate::{
+    Logic,
+    logic::modal::{self, Expr, World},
+    tableau::Branch,
+};
+
+/// Loop checking for the modal logics whose tableaux could otherwise go on
+/// forever, by comparing the formulas at each world of a branch.
+pub(crate) struct LoopCheck<L: Logic> {
+    /// The modal node within a node of the logic, if any.
+    pub(crate) modal: fn(&L::Node) -> Option<&modal::Node>,
+    /// Whether each world also gets the `□A` formulas of the worlds that
+    /// access it.
+    pub(crate) transitive: bool,
+    /// Whether a world is only blocked by one with exactly the same formulas,
+    /// rather than by any with at least the same ones.
+    pub(crate) exact: bool,
+}
+
+impl<L: Logic> LoopCheck<L> {
+    /// The formulas at `world` in the branch.
+    ///
+    /// In transitive logics, this also includes the `□A` formulas of every
+    /// world that accesses it, since `A` has to hold at every world it
+    /// accesses as well.
+    pub(crate) fn label<'b>(&self, branch: &'b impl Branch<L>, world: World) -> HashSet<&'b Expr>
+    where
+        L::Node: 'b,
+    {
+        let at = |world| {
+            branch
+                .at_world(world)
+                .filter_map(self.modal)
+                .filter_map(|node| match node {
+                    modal::Node::Expr { expr, .. } => Some(expr),
+                    modal::Node::Relation { .. } => None,
+                })
+        };
+
+        let mut label = at(world).collect::<HashSet<_>>();
+        if self.transitive {
+            let predecessors = branch
+                .relations_to(world)
+                .filter_map(self.modal)
+                .filter_map(|node| match node {
+                    modal::Node::Relation { from, .. } if *from != world => Some(*from),
+                    _ => None,
+                });
+            for predecessor in predecessors {
+                label.extend(at(predecessor).filter(|expr| matches!(expr, Expr::Necessity(_))));
+            }
+        }
+
+        label
+    }
+
+    /// The earliest world that blocks `world`, among those for which
+    /// `can_block` is true, if any.
+    ///
+    /// A world is blocked when an earlier world already has every formula it
+    /// has. Expanding the blocked world would just repeat what happened on the
+    /// earlier world, so instead the blocked world loops back to the worlds
+    /// the earlier one accesses.
+    pub(crate) fn blocker(
+        &self,
+        branch: &impl Branch<L>,
+        world: World,
+        can_block: impl Fn(World) -> bool,
+    ) -> Option<World> {
+        let label = self.label(branch, world);
+        (0..world.index())
+            .map(World::new)
+            .filter(|&other_world| can_block(other_world))
+            .find(|&other_world| {
+                let other = self.label(branch, other_world);
+                if self.exact {
+                    label == other
+                } else {
+                    label.is_subset(&other)
+                }
+            })
+    }
+}
@@ -0,0 +1,274 @@
+//! Evaluation of modal formulas in finite Kripke models.
+//!
+//! This is useful to double check that a countermodel read off an open branch
+//! does refute the original argument, or to play around with hand-written
+//! models.
+
+use std::fmt::{self, Write as _};
+
+use crate::{
+    logic::{
+        Model,
+        modal::{Expr, KripkeModel, World},
+        normal_modal::NormalModal,
+    },
+    tableau::dot_escape,
+};
+
+impl Model for KripkeModel {
+    type Expr = Expr;
+    type World = World;
+
+    /// Whether `expr` is true at `world`, using the standard semantics for `□`
+    /// and `◇`.
+    ///
+    /// Atoms without a value at `world` are taken to be false.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::{Model, modal::{Expr, KripkeModel, World}};
+    ///
+    /// let (w0, w1) = (World::new(0), World::new(1));
+    /// let mut model = KripkeModel::default();
+    /// model.worlds.extend([w0, w1]);
+    /// model.relation.insert((w0, w1));
+    /// model.valuation.entry(w1).or_default().insert("p".into(), true);
+    ///
+    /// assert!(model.evaluate(&"□p".parse::<Expr>().unwrap(), w0));
+    /// assert!(!model.evaluate(&"p".parse::<Expr>().unwrap(), w0));
+    /// // `w1` doesn't access any world, so everything is necessary there.
+    /// assert!(model.evaluate(&"□¬p".parse::<Expr>().unwrap(), w1));
+    /// ```
+    fn evaluate(&self, expr: &Expr, world: World) -> bool {
+        match expr {
+            Expr::Const(name) => self
+                .valuation
+                .get(&world)
+                .and_then(|valuation| valuation.get(name))
+                .copied()
+                .unwrap_or(false),
+            Expr::Not(p) => !self.evaluate(p, world),
+            Expr::And(p, q) => self.evaluate(p, world) && self.evaluate(q, world),
+            Expr::Or(p, q) => self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatImpl(p, q) => !self.evaluate(p, world) || self.evaluate(q, world),
+            Expr::MatEquiv(p, q) => self.evaluate(p, world) == self.evaluate(q, world),
+            Expr::Possibility(p) => self
+                .accessible_from(world)
+                .any(|other| self.evaluate(p, other)),
+            Expr::Necessity(p) => self
+                .accessible_from(world)
+                .all(|other| self.evaluate(p, other)),
+        }
+    }
+}
+
+impl KripkeModel {
+    /// The model as a Graphviz `digraph`, with the worlds as nodes labelled by
+    /// the atoms true at them, and the accessibility relation as edges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::modal;
+    ///
+    /// let tableau = modal::infer("◇p ⊢ □p");
+    /// assert_eq!(
+    ///     tableau.countermodels()[0].to_dot(),
+    ///     r#"digraph countermodel {
+    ///   w0 [label="0"];
+    ///   w1 [label="1\np"];
+    ///   w2 [label="2"];
+    ///   w0 -> w1;
+    ///   w0 -> w2;
+    /// }
+    /// "#,
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut output = String::new();
+        self.write_dot(&mut output)
+            .expect("writing to a string can't fail");
+        output
+    }
+
+    fn write_dot(&self, f: &mut String) -> fmt::Result {
+        writeln!(f, "digraph countermodel {{")?;
+        for world in &self.worlds {
+            let mut label = world.to_string();
+            let atoms = self
+                .valuation
+                .get(world)
+                .into_iter()
+                .flatten()
+                .filter(|(_, value)| **value)
+                .map(|(name, _)| &**name)
+                .collect::<Vec<_>>();
+            if !atoms.is_empty() {
+                write!(label, "\n{}", atoms.join(", "))?;
+            }
+
+            writeln!(f, "  w{world} [label=\"{}\"];", dot_escape(&label))?;
+        }
+
+        for (from, to) in &self.relation {
+            writeln!(f, "  w{from} -> w{to};")?;
+        }
+
+        writeln!(f, "}}")
+    }
+
+    /// Checks that the accessibility relation satisfies the frame conditions
+    /// of the given logic, returning the first condition that fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::{
+    ///     kripke::FrameViolation,
+    ///     modal::{KripkeModel, World},
+    ///     normal_modal::NormalModal,
+    /// };
+    ///
+    /// let (w0, w1) = (World::new(0), World::new(1));
+    /// let mut model = KripkeModel::default();
+    /// model.worlds.extend([w0, w1]);
+    /// model.relation.insert((w0, w1));
+    ///
+    /// assert_eq!(model.check_frame(&NormalModal::new()), Ok(()));
+    /// assert_eq!(
+    ///     model.check_frame(&NormalModal::new().symmetric()),
+    ///     Err(FrameViolation::Symmetric(w0, w1)),
+    /// );
+    /// ```
+    pub fn check_frame(&self, logic: &NormalModal) -> Result<(), FrameViolation> {
+        if logic.reflexive
+            && let Some(&world) = self
+                .worlds
+                .iter()
+                .find(|world| !self.relation.contains(&(**world, **world)))
+        {
+            return Err(FrameViolation::Reflexive(world));
+        }
+
+        if logic.symmetric
+            && let Some(&(from, to)) = self
+                .relation
+                .iter()
+                .find(|(from, to)| !self.relation.contains(&(*to, *from)))
+        {
+            return Err(FrameViolation::Symmetric(from, to));
+        }
+
+        if logic.transitive {
+            for &(i, j) in &self.relation {
+                for k in self.accessible_from(j) {
+                    if !self.relation.contains(&(i, k)) {
+                        return Err(FrameViolation::Transitive(i, j, k));
+                    }
+                }
+            }
+        }
+
+        if logic.extendable
+            && let Some(&world) = self
+                .worlds
+                .iter()
+                .find(|world| self.accessible_from(**world).next().is_none())
+        {
+            return Err(FrameViolation::Extendable(world));
+        }
+
+        Ok(())
+    }
+
+    /// Adds the relations needed to satisfy the frame conditions of the
+    /// given logic, except for extendability.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::{
+    ///     modal::{KripkeModel, World},
+    ///     normal_modal,
+    /// };
+    ///
+    /// let (w0, w1, w2) = (World::new(0), World::new(1), World::new(2));
+    /// let mut model = KripkeModel::default();
+    /// model.worlds.extend([w0, w1, w2]);
+    /// model.relation.extend([(w0, w1), (w1, w2)]);
+    ///
+    /// model.close_frame(&normal_modal::S4);
+    /// assert_eq!(model.check_frame(&normal_modal::S4), Ok(()));
+    /// assert!(model.relation.contains(&(w0, w2)));
+    /// ```
+    pub fn close_frame(&mut self, logic: &NormalModal) {
+        if logic.reflexive {
+            self.relation
+                .extend(self.worlds.iter().map(|world| (*world, *world)));
+        }
+
+        loop {
+            let mut new_relations = Vec::new();
+            for &(from, to) in &self.relation {
+                if logic.symmetric && !self.relation.contains(&(to, from)) {
+                    new_relations.push((to, from));
+                }
+
+                if logic.transitive {
+                    new_relations.extend(
+                        self.accessible_from(to)
+                            .filter(|k| !self.relation.contains(&(from, *k)))
+                            .map(|k| (from, k)),
+                    );
+                }
+            }
+
+            if new_relations.is_empty() {
+                break;
+            }
+
+            self.relation.extend(new_relations);
+        }
+    }
+
+    /// Iterator over the worlds that `world` can access.
+    pub fn accessible_from(&self, world: World) -> impl Iterator<Item = World> + '_ {
+        self.relation
+            .iter()
+            .filter(move |(from, _)| *from == world)
+            .map(|(_, to)| *to)
+    }
+}
+
+/// A frame condition of a [`NormalModal`] logic that a [`KripkeModel`] doesn't
+/// satisfy, along with the worlds that show it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameViolation {
+    /// ρ, `w` doesn't access itself.
+    Reflexive(World),
+    /// σ, `w1 R w2` but not `w2 R w1`.
+    Symmetric(World, World),
+    /// τ, `w1 R w2` and `w2 R w3` but not `w1 R w3`.
+    Transitive(World, World, World),
+    /// η, `w` doesn't access any world.
+    Extendable(World),
+}
+
+impl fmt::Display for FrameViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reflexive(w) => write!(f, "not reflexive (ρ): missing {w}r{w}"),
+            Self::Symmetric(w1, w2) => {
+                write!(f, "not symmetric (σ): {w1}r{w2} but not {w2}r{w1}")
+            }
+            Self::Transitive(w1, w2, w3) => write!(
+                f,
+                "not transitive (τ): {w1}r{w2} and {w2}r{w3} but not {w1}r{w3}"
+            ),
+            Self::Extendable(w) => write!(f, "not extendable (η): {w} accesses no world"),
+        }
+    }
+}
+
+impl std::error::Error for FrameViolation {}
@@ -1,4 +1,8 @@
-use winnow::{ModalResult, Parser as _, combinator::alt, token::take_while};
+use winnow::{
+    ModalResult, Parser as _,
+    combinator::{alt, not, repeat},
+    token::none_of,
+};
 
 mod unicode {
     pub const NOT: char = '¬';
@@ -6,8 +10,14 @@ mod unicode {
     pub const OR: &str = "∨";
     pub const MAT_IMPL: char = '⊃';
     pub const MAT_EQUIV: &str = "≡";
+    pub const CONDITIONAL: &str = "≻";
+    pub const IMPLICATION: &str = "→";
     pub const POSSIB: &str = "◇";
     pub const NECESS: &str = "□";
+    pub const FUTURE_NECESS: &str = "[F]";
+    pub const FUTURE_POSSIB: &str = "⟨F⟩";
+    pub const PAST_NECESS: &str = "[P]";
+    pub const PAST_POSSIB: &str = "⟨P⟩";
 }
 
 #[cfg(feature = "wasm")]
@@ -23,32 +33,70 @@ pub enum Symbol {
     Or,
     MatImpl,
     MatEquiv,
+    Conditional,
+    Implication,
     Possib,
     Necess,
+    FutureNecess,
+    FuturePossib,
+    PastNecess,
+    PastPossib,
 }
 
 impl Symbol {
-    pub fn symbol(self) -> char {
+    /// The symbol as a string, such as `∧`.
+    pub fn unicode_str(self) -> &'static str {
         match self {
-            Symbol::Not => NOT,
-            Symbol::And => AND.chars().next().unwrap(),
-            Symbol::Or => OR.chars().next().unwrap(),
-            Symbol::MatImpl => MAT_IMPL,
-            Symbol::MatEquiv => MAT_EQUIV.chars().next().unwrap(),
-            Symbol::Possib => POSSIB.chars().next().unwrap(),
-            Symbol::Necess => NECESS.chars().next().unwrap(),
+            Symbol::Not => "¬",
+            Symbol::And => AND,
+            Symbol::Or => OR,
+            Symbol::MatImpl => "⊃",
+            Symbol::MatEquiv => MAT_EQUIV,
+            Symbol::Conditional => CONDITIONAL,
+            Symbol::Implication => IMPLICATION,
+            Symbol::Possib => POSSIB,
+            Symbol::Necess => NECESS,
+            Symbol::FutureNecess => FUTURE_NECESS,
+            Symbol::FuturePossib => FUTURE_POSSIB,
+            Symbol::PastNecess => PAST_NECESS,
+            Symbol::PastPossib => PAST_POSSIB,
         }
     }
 
-    pub fn ascii_str(&self) -> &str {
+    pub fn ascii_str(&self) -> &'static str {
         match self {
             Symbol::Not => "!",
             Symbol::And => "&&",
             Symbol::Or => "||",
             Symbol::MatImpl => ">",
             Symbol::MatEquiv => "==",
+            Symbol::Conditional => "=>",
+            Symbol::Implication => "->",
             Symbol::Possib => "<>",
             Symbol::Necess => "[]",
+            Symbol::FutureNecess => "[F]",
+            Symbol::FuturePossib => "<F>",
+            Symbol::PastNecess => "[P]",
+            Symbol::PastPossib => "<P>",
+        }
+    }
+
+    /// The LaTeX command for the symbol, such as `\land`.
+    pub fn latex(self) -> &'static str {
+        match self {
+            Symbol::Not => "\\neg",
+            Symbol::And => "\\land",
+            Symbol::Or => "\\lor",
+            Symbol::MatImpl => "\\supset",
+            Symbol::MatEquiv => "\\equiv",
+            Symbol::Conditional => ">",
+            Symbol::Implication => "\\rightarrow",
+            Symbol::Possib => "\\Diamond",
+            Symbol::Necess => "\\Box",
+            Symbol::FutureNecess => "[F]",
+            Symbol::FuturePossib => "\\langle F \\rangle",
+            Symbol::PastNecess => "[P]",
+            Symbol::PastPossib => "\\langle P \\rangle",
         }
     }
 
@@ -59,8 +107,14 @@ impl Symbol {
             Symbol::Or => "Or",
             Symbol::MatImpl => "Material implication",
             Symbol::MatEquiv => "Material equivalence",
+            Symbol::Conditional => "Conditional",
+            Symbol::Implication => "Implication",
             Symbol::Possib => "Possibility",
             Symbol::Necess => "Necessity",
+            Symbol::FutureNecess => "Always in the future",
+            Symbol::FuturePossib => "Sometime in the future",
+            Symbol::PastNecess => "Always in the past",
+            Symbol::PastPossib => "Sometime in the past",
         }
     }
 
@@ -71,22 +125,68 @@ impl Symbol {
             Symbol::Or => alt([OR, "||"]).map(|_| ()).parse_next(input),
             Symbol::MatImpl => alt([MAT_IMPL, '>']).map(|_| ()).parse_next(input),
             Symbol::MatEquiv => alt([MAT_EQUIV, "=="]).map(|_| ()).parse_next(input),
+            Symbol::Conditional => alt([CONDITIONAL, "=>"]).map(|_| ()).parse_next(input),
+            Symbol::Implication => alt([IMPLICATION, "->"]).map(|_| ()).parse_next(input),
             Symbol::Possib => alt([POSSIB, "<>"]).map(|_| ()).parse_next(input),
             Symbol::Necess => alt([NECESS, "[]"]).map(|_| ()).parse_next(input),
+            Symbol::FutureNecess => FUTURE_NECESS.map(|_| ()).parse_next(input),
+            Symbol::FuturePossib => alt([FUTURE_POSSIB, "<F>"]).map(|_| ()).parse_next(input),
+            Symbol::PastNecess => PAST_NECESS.map(|_| ()).parse_next(input),
+            Symbol::PastPossib => alt([PAST_POSSIB, "<P>"]).map(|_| ()).parse_next(input),
+        }
+    }
+
+    /// How tightly the symbol binds, higher meaning tighter: `¬` and the modal
+    /// and tense operators bind the tightest, then `∧`, `∨`, `⊃` (and the
+    /// conditional `≻` and implication `→`) and finally `≡`.
+    ///
+    /// So `¬p ∧ q ∨ r ⊃ s` is `((¬p ∧ q) ∨ r) ⊃ s`.
+    pub const fn precedence(self) -> u8 {
+        match self {
+            Symbol::Not
+            | Symbol::Possib
+            | Symbol::Necess
+            | Symbol::FutureNecess
+            | Symbol::FuturePossib
+            | Symbol::PastNecess
+            | Symbol::PastPossib => 5,
+            Symbol::And => 4,
+            Symbol::Or => 3,
+            Symbol::MatImpl | Symbol::Conditional | Symbol::Implication => 2,
+            Symbol::MatEquiv => 1,
         }
     }
 
+    /// Whether the symbol goes between two expressions.
+    pub const fn is_binary(self) -> bool {
+        matches!(
+            self,
+            Symbol::And
+                | Symbol::Or
+                | Symbol::MatImpl
+                | Symbol::MatEquiv
+                | Symbol::Conditional
+                | Symbol::Implication
+        )
+    }
+
     pub fn iter() -> impl Iterator<Item = Symbol> {
         [
             Symbol::Not,
             Symbol::And,
             Symbol::Or,
             Symbol::MatEquiv,
+            Symbol::Conditional,
+            Symbol::Implication,
             Symbol::Possib,
             Symbol::Necess,
-            // NOTE: Material implication goes after <> because in ascii it's >
-            // and we want to first replace the <> and after the > otherwise we
-            // get wrong symbols.
+            Symbol::FutureNecess,
+            Symbol::FuturePossib,
+            Symbol::PastNecess,
+            Symbol::PastPossib,
+            // NOTE: Material implication goes after <> (and <F> and <P>)
+            // because in ascii it's > and we want to first replace the <> and
+            // after the > otherwise we get wrong symbols.
             // FIXME: This does work but is very fragile...
             Symbol::MatImpl,
         ]
@@ -94,13 +194,18 @@ impl Symbol {
     }
 }
 
+/// The name of an atom, which ends at any symbol. A `-` only ends it as part
+/// of `->`, so that `a-b` is still one atom.
 pub fn ident<'a>(input: &mut &'a str) -> ModalResult<&'a str> {
-    take_while(1.., |char| !SYMBOL_START.contains(&char)).parse_next(input)
+    repeat::<_, _, (), _, _>(1.., (not("->"), none_of(SYMBOL_START)))
+        .take()
+        .parse_next(input)
 }
 
-/// Characters with which a symbol can start.
-pub const SYMBOL_START: &'static [char] = &[
-    '(', ')', ' ', '¬', '∨', '∧', '⊃', '≡', '□', '◇', '!', '|', '&', '>', '=', '[', '<', ' ',
+/// Characters with which a symbol can start, other than the `-` of `->`.
+pub const SYMBOL_START: &[char] = &[
+    '(', ')', ' ', '¬', '∨', '∧', '⊃', '≡', '≻', '→', '□', '◇', '⟨', '!', '|', '&', '>', '=', '[',
+    '<', ' ',
 ];
 
 // Standalone methods for wasm since javascript doesn't have enum methods
@@ -109,9 +214,9 @@ mod wasm {
     use super::Symbol;
     use wasm_bindgen::prelude::*;
 
-    #[wasm_bindgen(js_name = symbolChar)]
-    pub fn symbol_char(p: Symbol) -> char {
-        p.symbol()
+    #[wasm_bindgen(js_name = symbolUnicodeStr)]
+    pub fn symbol_unicode_str(p: Symbol) -> String {
+        p.unicode_str().to_string()
     }
 
     #[wasm_bindgen(js_name = symbolAsciiStr)]
@@ -1,19 +1,38 @@
 mod impls;
-use std::borrow::Cow;
+use std::{borrow::Cow, hash::Hash};
 
 pub use impls::*;
 
 mod dynamic;
+pub mod kripke;
+mod model;
+pub use model::Model;
 #[cfg(feature = "parse")]
 mod lexer;
-
-use crate::{PartialTableau, tableau::Branch};
+#[cfg(feature = "parse")]
+mod parser;
+#[cfg(feature = "parse")]
+pub use lexer::Symbol;
+#[cfg(feature = "parse")]
+pub use parser::{Associativity, Construct, Grammar, ParseError, parse};
+mod printer;
+pub use printer::{Inspect, Notation, Print, Printer, Term};
+mod propositional;
+pub use propositional::Propositional;
+
+use crate::{
+    PartialTableau,
+    logic::modal::World,
+    tableau::{Branch, NodeId},
+};
 
 pub use dynamic::*;
 
 pub trait Logic {
-    type Node: Clone;
+    type Node: Clone + Eq + Hash;
     type Expr;
+    /// A model that makes the premises true and the conclusion false.
+    type Countermodel;
 
     fn symbol(&self) -> Cow<'static, str>;
 
@@ -21,8 +40,27 @@ pub trait Logic {
     where
         Self: Sized;
 
-    // TODO: Document that it is guaranteed that the ancestors won't contain a contradiction.
-    fn has_contradiction(&self, branch: impl Branch<Self>) -> bool
+    /// Checks whether the leaf of the branch contradicts any of its ancestors,
+    /// returning the id of the contradicting ancestor.
+    ///
+    /// It is guaranteed that the ancestors of the leaf don't contain a
+    /// contradiction among themselves, since the branch would already be closed.
+    fn has_contradiction(&self, branch: impl Branch<Self>) -> Option<NodeId>
+    where
+        Self: Sized;
+
+    /// The name of the rule that `inference` results from when inferring
+    /// `node`, such as "¬∧" or "□".
+    fn rule_name(
+        &self,
+        _node: &Self::Node,
+        _inference: InferenceRule<&Self::Node>,
+    ) -> Cow<'static, str> {
+        Cow::Borrowed("")
+    }
+
+    /// Reads the countermodel off an open, completed branch.
+    fn countermodel(&self, branch: impl Branch<Self>) -> Self::Countermodel
     where
         Self: Sized;
 
@@ -34,6 +72,60 @@ pub trait Logic {
     {
     }
 
+    /// Inference to carry out on a branch once every node in it has been
+    /// inferred, such as extending to a fresh world in logics with η.
+    ///
+    /// Inference stops once this returns nothing new for every open branch.
+    fn saturate(&self, _branch: impl Branch<Self>) -> InferenceRule<Self::Node>
+    where
+        Self: Sized,
+    {
+        InferenceRule::none()
+    }
+
+    /// Whether `node` has to be inferred again when `new_node` gets added
+    /// below it, even if it has already been inferred.
+    ///
+    /// This is needed for rules that depend on the rest of the branch, such as
+    /// `□A, i`, which has to be applied again for every new `i r j`.
+    fn reinfer_on(&self, _node: &Self::Node, _new_node: &Self::Node) -> bool {
+        false
+    }
+
+    /// Whether [`Self::reinfer_on`] can ever be true, so that logics without
+    /// such rules don't have to look through the branch for every new node.
+    ///
+    /// Logics that override [`Self::reinfer_on`] have to return `true`.
+    fn reinfers(&self) -> bool {
+        false
+    }
+
+    /// The number of worlds the node refers to, i.e., one more than the
+    /// highest world index that appears in it. Logics without worlds
+    /// return 0.
+    ///
+    /// Used to limit the size of tableaux, see [`crate::Limits`].
+    fn worlds_in(&self, _node: &Self::Node) -> usize {
+        0
+    }
+
+    /// Where `node` stands among the worlds of its branch, if anywhere.
+    ///
+    /// Branches index nodes by their place, so that rules can look up the
+    /// formulas at a world or the worlds it accesses without walking the
+    /// whole branch, see [`Branch::at_world`] and [`Branch::relations_from`].
+    fn place(&self, _node: &Self::Node) -> Option<Place> {
+        None
+    }
+
+    /// Whether `node` can be part of a tableau of this logic.
+    ///
+    /// Always true unless the logic is only known at runtime, such as
+    /// [`DynLogic`], whose nodes have to be of the same logic as itself.
+    fn owns_node(&self, _node: &Self::Node) -> bool {
+        true
+    }
+
     /// The priority in terms of node expansion.
     ///
     /// If higher, it means to expand earlier.
@@ -56,13 +148,25 @@ pub trait Logic {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Where a node stands among the worlds of its branch, see [`Logic::place`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Place {
+    /// The node is about a single world, such as `A, i`.
+    At(World),
+    /// The node relates two worlds, such as `i r j`.
+    Relation { from: World, to: World },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InferenceRule<E> {
     None,
     Single(E),
     Split([E; 2]),
     Chain(Vec<E>),
     SplitAndChain([[E; 2]; 2]),
+    /// Any number of branches, each with its own chain of nodes.
+    SplitChains(Vec<Vec<E>>),
 }
 
 impl<E> InferenceRule<E> {
@@ -86,17 +190,40 @@ impl<E> InferenceRule<E> {
         Self::SplitAndChain([left_chain, right_chain])
     }
 
+    pub const fn split_chains(chains: Vec<Vec<E>>) -> Self {
+        Self::SplitChains(chains)
+    }
+
+    pub fn as_ref(&self) -> InferenceRule<&E> {
+        match self {
+            Self::None => InferenceRule::None,
+            Self::Single(a) => InferenceRule::Single(a),
+            Self::Split([a, b]) => InferenceRule::Split([a, b]),
+            Self::Chain(exprs) => InferenceRule::Chain(exprs.iter().collect()),
+            Self::SplitAndChain([[la, lb], [ra, rb]]) => {
+                InferenceRule::SplitAndChain([[la, lb], [ra, rb]])
+            }
+            Self::SplitChains(chains) => InferenceRule::SplitChains(
+                chains.iter().map(|chain| chain.iter().collect()).collect(),
+            ),
+        }
+    }
+
     pub fn map<F>(self, f: impl Fn(E) -> F) -> InferenceRule<F> {
         match self {
             Self::None => InferenceRule::None,
             Self::Single(a) => InferenceRule::Single(f(a)),
             Self::Split([a, b]) => InferenceRule::Split([f(a), f(b)]),
-            Self::Chain(exprs) => {
-                InferenceRule::Chain(exprs.into_iter().map(|expr| f(expr)).collect())
-            }
+            Self::Chain(exprs) => InferenceRule::Chain(exprs.into_iter().map(&f).collect()),
             Self::SplitAndChain([[la, lb], [ra, rb]]) => {
                 InferenceRule::SplitAndChain([[f(la), f(lb)], [f(ra), f(rb)]])
             }
+            Self::SplitChains(chains) => InferenceRule::SplitChains(
+                chains
+                    .into_iter()
+                    .map(|chain| chain.into_iter().map(&f).collect())
+                    .collect(),
+            ),
         }
     }
 }
@@ -0,0 +1,125 @@
+//! Evaluating expressions in models, such as the countermodels read off open
+//! branches.
+
+/// A model in which the expressions of some logic are true or not.
+///
+/// Every countermodel of the logics of this crate is one, other than
+/// [`DynCountermodel`](super::DynCountermodel).
+pub trait Model {
+    type Expr;
+    /// Where expressions are evaluated, which is a
+    /// [`World`](super::modal::World) in logics with worlds and `()` in the
+    /// others.
+    type World: Copy;
+
+    /// Whether `expr` is true (or designated, in many-valued logics) at
+    /// `world`.
+    fn evaluate(&self, expr: &Self::Expr, world: Self::World) -> bool;
+
+    /// Whether the model refutes the argument at `world`, i.e., whether every
+    /// premise is true and the conclusion isn't there.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use tableaux::logic::{self, Model, modal::{Expr, World}};
+    ///
+    /// let tableau = logic::modal::infer("◇p, ◇q ⊢ ◇(p ∧ q)");
+    /// let premises = [Expr::from_str("◇p").unwrap(), Expr::from_str("◇q").unwrap()];
+    /// let conclusion = Expr::from_str("◇(p ∧ q)").unwrap();
+    ///
+    /// for countermodel in tableau.countermodels() {
+    ///     assert!(countermodel.refutes(&premises, &conclusion, World::ZERO));
+    /// }
+    /// ```
+    fn refutes<'a>(
+        &self,
+        premises: impl IntoIterator<Item = &'a Self::Expr>,
+        conclusion: &Self::Expr,
+        world: Self::World,
+    ) -> bool
+    where
+        Self::Expr: 'a,
+    {
+        premises
+            .into_iter()
+            .all(|premise| self.evaluate(premise, world))
+            && !self.evaluate(conclusion, world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt, str::FromStr};
+
+    use super::*;
+    use crate::logic::{
+        Logic,
+        classical::Classical,
+        conditional,
+        intuitionist::Intuitionist,
+        many_valued,
+        modal::{Modal, World},
+        non_normal, normal_modal, tense,
+    };
+
+    /// Checks that the argument doesn't hold in `logic`, and that every
+    /// countermodel refutes it at `world`.
+    fn assert_refuted<L>(
+        logic: L,
+        premises: &[&str],
+        conclusion: &str,
+        world: <L::Countermodel as Model>::World,
+    ) where
+        L: Logic,
+        L::Expr: FromStr + Clone,
+        <L::Expr as FromStr>::Err: fmt::Debug,
+        L::Countermodel: Model<Expr = L::Expr> + fmt::Display,
+    {
+        let argument = format!("{} ⊢ {conclusion}", premises.join(", "));
+        let parse = |expr: &str| expr.parse::<L::Expr>().unwrap();
+        let premises = premises
+            .iter()
+            .map(|premise| parse(premise))
+            .collect::<Vec<_>>();
+        let conclusion = parse(conclusion);
+
+        let countermodels = logic
+            .tableau(premises.clone(), conclusion.clone())
+            .infer()
+            .countermodels();
+        assert!(!countermodels.is_empty(), "{argument} holds");
+        for countermodel in countermodels {
+            assert!(
+                countermodel.refutes(&premises, &conclusion, world),
+                "{argument} isn't refuted by {countermodel}"
+            );
+        }
+    }
+
+    #[test]
+    fn countermodels_refute() {
+        assert_refuted(Classical {}, &["p ∨ q"], "p", ());
+
+        assert_refuted(Modal {}, &["◇p", "◇q"], "◇(p ∧ q)", World::ZERO);
+        assert_refuted(normal_modal::T, &["p"], "□p", World::ZERO);
+        assert_refuted(normal_modal::S4, &["◇p"], "□◇p", World::ZERO);
+        assert_refuted(non_normal::N, &[], "□□(p ⊃ p)", World::ZERO);
+        assert_refuted(non_normal::S2, &["□p"], "□□p", World::ZERO);
+
+        assert_refuted(tense::KT, &["⟨P⟩⟨F⟩p"], "⟨P⟩p ∨ p ∨ ⟨F⟩p", World::ZERO);
+        assert_refuted(tense::KT, &["⟨F⟩p", "⟨F⟩q"], "⟨F⟩(p ∧ q)", World::ZERO);
+
+        assert_refuted(Intuitionist {}, &["¬(p ∧ q)"], "¬p ∨ ¬q", World::ZERO);
+        assert_refuted(Intuitionist {}, &[], "p ∨ ¬p", World::ZERO);
+
+        assert_refuted(conditional::C, &[], "p ≻ p", World::ZERO);
+        assert_refuted(conditional::C_PLUS, &["p ≻ q"], "¬q ≻ ¬p", World::ZERO);
+
+        assert_refuted(many_valued::K3, &[], "p ∨ ¬p", ());
+        assert_refuted(many_valued::LP, &["p", "¬p ∨ q"], "q", ());
+        assert_refuted(many_valued::L3, &[], "(p ∧ ¬p) → q", ());
+        assert_refuted(many_valued::RM3, &["p"], "q → p", ());
+    }
+}
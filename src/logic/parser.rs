@@ -0,0 +1,431 @@
+//! The parser shared by the expressions of every logic.
+//!
+//! Each logic accepts some set of [`Symbol`]s, such as
+//! [`Classical::symbols`](crate::logic::classical::Classical::symbols), and
+//! its expression type implements [`Construct`] to be built from them.
+
+use std::{fmt, ops::Range};
+
+use winnow::{
+    ModalResult, Parser as _,
+    ascii::space0,
+    combinator::{cut_err, delimited},
+    error::{ContextError, ErrMode, StrContext, StrContextValue},
+    stream::Stream as _,
+};
+
+use super::lexer::{Symbol, ident};
+
+/// Building an expression out of the pieces found by [`parse`].
+///
+/// Only symbols in the set given to [`parse`] are passed, so the others can
+/// be treated as unreachable.
+pub trait Construct: Sized {
+    /// A propositional constant, such as `p`.
+    fn constant(name: &str) -> Self;
+    /// A unary operator applied to `operand`, such as `¬p`.
+    fn unary(symbol: Symbol, operand: Self) -> Self;
+    /// A binary connective between `lhs` and `rhs`, such as `p ∧ q`.
+    fn binary(symbol: Symbol, lhs: Self, rhs: Self) -> Self;
+}
+
+/// Parses an expression made of constants, parentheses and the given
+/// `symbols`, grouping chained connectives as given by `grammar`.
+///
+/// Any other symbol is rejected, with [`ParseError::unsupported`] set.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::logic::{
+///     Grammar, Symbol, classical::{Classical, Expr}, modal::Modal, parse,
+/// };
+///
+/// let expr = parse::<Expr>("¬p ∨ q", Classical::symbols(), &Grammar::new());
+/// assert_eq!(expr, "¬p ∨ q".parse());
+///
+/// let err = parse::<Expr>("p ∧ □q", Classical::symbols(), &Grammar::new()).unwrap_err();
+/// assert_eq!(err.unsupported, Some(Symbol::Necess));
+/// assert_eq!(err.span, 6..9);
+/// assert_eq!(err.to_string(), "`□` (Necessity) isn't supported by this logic");
+///
+/// assert!(Modal::symbols().contains(&Symbol::Necess));
+/// ```
+pub fn parse<E: Construct>(
+    s: &str,
+    symbols: &[Symbol],
+    grammar: &Grammar,
+) -> Result<E, ParseError> {
+    let parser = Parser { symbols, grammar };
+    (|input: &mut &str| parser.expr(input))
+        .parse(s)
+        .map_err(|err| ParseError::new(s, &err))
+}
+
+/// Label of the errors caused by symbols that aren't supported.
+const UNSUPPORTED: &str = "unsupported symbol";
+
+#[derive(Clone, Copy)]
+struct Parser<'a> {
+    symbols: &'a [Symbol],
+    grammar: &'a Grammar,
+}
+
+impl Parser<'_> {
+    /// A whole expression, with any surrounding space.
+    fn expr<E: Construct>(&self, input: &mut &str) -> ModalResult<E> {
+        let output = self.binary(input, 0)?;
+        space0.parse_next(input)?;
+        Ok(output)
+    }
+
+    /// A constant, an expression in parentheses or a unary operator applied
+    /// to one of those, with any surrounding space.
+    fn single<E: Construct>(&self, input: &mut &str) -> ModalResult<E> {
+        space0.parse_next(input)?;
+
+        let output = if input.starts_with('(') {
+            delimited(
+                '(',
+                cut_err(|input: &mut &str| self.expr(input)),
+                cut_err(')'.context(expected_char(')'))),
+            )
+            .parse_next(input)?
+        } else if let Ok(name) = ident.parse_next(input) {
+            E::constant(name)
+        } else if let Some(symbol) = self.symbol(input, |symbol| !symbol.is_binary())? {
+            let operand = cut_err(|input: &mut &str| self.single(input)).parse_next(input)?;
+            E::unary(symbol, operand)
+        } else {
+            let mut error = ContextError::new();
+            error.push(expected_expr());
+            return Err(ErrMode::Backtrack(error));
+        };
+
+        space0.parse_next(input)?;
+        Ok(output)
+    }
+
+    /// Parses operands separated by binary connectives, grouping them
+    /// according to the precedence of each connective and the associativity
+    /// in the grammar.
+    ///
+    /// Only connectives with a precedence of at least `min_precedence` are
+    /// consumed, so this should be called with 0 to parse a whole expression.
+    fn binary<E: Construct>(&self, input: &mut &str, min_precedence: u8) -> ModalResult<E> {
+        let mut lhs = self.single(input)?;
+        let mut last: Option<Symbol> = None;
+
+        loop {
+            let checkpoint = input.checkpoint();
+            let Some(symbol) = self.symbol(input, Symbol::is_binary)? else {
+                break;
+            };
+
+            if symbol.precedence() < min_precedence {
+                input.reset(&checkpoint);
+                break;
+            }
+
+            let associativity = self.grammar.associativity_of(symbol);
+            if last == Some(symbol) && associativity == Associativity::Neither {
+                input.reset(&checkpoint);
+                let mut error = ContextError::new();
+                error.push(StrContext::Expected(StrContextValue::Description(
+                    "parentheses around the chain",
+                )));
+                return Err(ErrMode::Cut(error));
+            }
+
+            let next_precedence = match associativity {
+                Associativity::Right => symbol.precedence(),
+                Associativity::Left | Associativity::Neither => symbol.precedence() + 1,
+            };
+
+            // Having seen a connective, there must be an operand after it.
+            let rhs = self.binary(input, next_precedence).map_err(ErrMode::cut)?;
+            lhs = E::binary(symbol, lhs, rhs);
+            last = Some(symbol);
+        }
+
+        Ok(lhs)
+    }
+
+    /// Consumes a symbol that satisfies `filter`, if there is one, failing if
+    /// it isn't supported.
+    fn symbol(
+        &self,
+        input: &mut &str,
+        filter: impl Fn(Symbol) -> bool,
+    ) -> ModalResult<Option<Symbol>> {
+        let checkpoint = input.checkpoint();
+        let Some(symbol) = Symbol::iter()
+            .filter(|symbol| filter(*symbol))
+            .find(|symbol| {
+                input.reset(&checkpoint);
+                symbol.parser().parse_next(input).is_ok()
+            })
+        else {
+            input.reset(&checkpoint);
+            return Ok(None);
+        };
+
+        if !self.symbols.contains(&symbol) {
+            input.reset(&checkpoint);
+            let mut error = ContextError::new();
+            error.push(StrContext::Label(UNSUPPORTED));
+            return Err(ErrMode::Cut(error));
+        }
+
+        Ok(Some(symbol))
+    }
+}
+
+/// How a chain of the same binary connective without parentheses, like
+/// `p ⊃ q ⊃ r`, gets grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    /// `(p ⊃ q) ⊃ r`.
+    Left,
+    /// `p ⊃ (q ⊃ r)`.
+    Right,
+    /// Chains are rejected, so parentheses are required.
+    Neither,
+}
+
+/// Associativity of each binary connective when parsing expressions.
+///
+/// Precedence is fixed (see [`Symbol::precedence`]), so this only matters
+/// when the same connective is chained.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::logic::{Associativity, Grammar, Symbol, classical::Expr};
+///
+/// let grammar = Grammar::new().associativity(Symbol::MatImpl, Associativity::Left);
+/// assert_eq!(
+///     Expr::parse_with("p ⊃ q ⊃ r", &grammar),
+///     "(p ⊃ q) ⊃ r".parse(),
+/// );
+///
+/// let grammar = Grammar::new().associativity(Symbol::MatEquiv, Associativity::Neither);
+/// assert!(Expr::parse_with("p ≡ q ≡ r", &grammar).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grammar {
+    pub and: Associativity,
+    pub or: Associativity,
+    pub mat_impl: Associativity,
+    pub mat_equiv: Associativity,
+    pub conditional: Associativity,
+    pub implication: Associativity,
+}
+
+impl Grammar {
+    /// Every connective is right associative.
+    pub const fn new() -> Self {
+        Self {
+            and: Associativity::Right,
+            or: Associativity::Right,
+            mat_impl: Associativity::Right,
+            mat_equiv: Associativity::Right,
+            conditional: Associativity::Right,
+            implication: Associativity::Right,
+        }
+    }
+
+    /// Sets the associativity of a binary connective. Other symbols are
+    /// ignored.
+    pub const fn associativity(self, symbol: Symbol, associativity: Associativity) -> Self {
+        match symbol {
+            Symbol::And => Self {
+                and: associativity,
+                ..self
+            },
+            Symbol::Or => Self {
+                or: associativity,
+                ..self
+            },
+            Symbol::MatImpl => Self {
+                mat_impl: associativity,
+                ..self
+            },
+            Symbol::MatEquiv => Self {
+                mat_equiv: associativity,
+                ..self
+            },
+            Symbol::Conditional => Self {
+                conditional: associativity,
+                ..self
+            },
+            Symbol::Implication => Self {
+                implication: associativity,
+                ..self
+            },
+            Symbol::Not
+            | Symbol::Possib
+            | Symbol::Necess
+            | Symbol::FutureNecess
+            | Symbol::FuturePossib
+            | Symbol::PastNecess
+            | Symbol::PastPossib => self,
+        }
+    }
+
+    /// The associativity of `symbol`, which is [`Associativity::Right`] for
+    /// unary symbols.
+    pub const fn associativity_of(&self, symbol: Symbol) -> Associativity {
+        match symbol {
+            Symbol::And => self.and,
+            Symbol::Or => self.or,
+            Symbol::MatImpl => self.mat_impl,
+            Symbol::MatEquiv => self.mat_equiv,
+            Symbol::Conditional => self.conditional,
+            Symbol::Implication => self.implication,
+            Symbol::Not
+            | Symbol::Possib
+            | Symbol::Necess
+            | Symbol::FutureNecess
+            | Symbol::FuturePossib
+            | Symbol::PastNecess
+            | Symbol::PastPossib => Associativity::Right,
+        }
+    }
+}
+
+impl Default for Grammar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Labels a parser failure as expecting an expression.
+fn expected_expr() -> StrContext {
+    StrContext::Expected(StrContextValue::Description("expression"))
+}
+
+/// Labels a parser failure as expecting the given character.
+fn expected_char(char: char) -> StrContext {
+    StrContext::Expected(StrContextValue::CharLiteral(char))
+}
+
+/// An error found while parsing an expression.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::logic::classical::Expr;
+///
+/// let err = "p ∧ (q r)".parse::<Expr>().unwrap_err();
+/// assert_eq!(err.span, 9..10);
+/// assert_eq!(err.expected, ["`)`"]);
+/// assert_eq!(err.found.as_deref(), Some("r"));
+/// assert_eq!(err.to_string(), "expected `)`, found `r`");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte range of the input where the error is. It's empty if the error
+    /// is at the end of the input.
+    pub span: Range<usize>,
+    /// Descriptions of what could have been there instead, such as
+    /// "expression" or "`)`".
+    pub expected: Vec<String>,
+    /// What was found instead, or [`None`] at the end of the input.
+    pub found: Option<String>,
+    /// The symbol that was found, if the logic doesn't support it.
+    pub unsupported: Option<Symbol>,
+}
+
+impl ParseError {
+    /// Converts an error of the underlying parser for `input`.
+    fn new(input: &str, error: &winnow::error::ParseError<&str, ContextError>) -> Self {
+        let offset = error.offset();
+        let rest = &input[offset..];
+        let symbol = Symbol::iter().find_map(|symbol| {
+            let mut token = rest;
+            symbol
+                .parser()
+                .parse_next(&mut token)
+                .ok()
+                .map(|()| (symbol, rest.len() - token.len()))
+        });
+
+        let found = rest.chars().next().map(|first| {
+            let mut token = rest;
+            let len = if let Ok(ident) = ident(&mut token) {
+                ident.len()
+            } else if let Some((_, len)) = symbol {
+                len
+            } else {
+                first.len_utf8()
+            };
+
+            rest[..len].to_string()
+        });
+
+        let unsupported = symbol.map(|(symbol, _)| symbol).filter(|_| {
+            error
+                .inner()
+                .context()
+                .any(|context| *context == StrContext::Label(UNSUPPORTED))
+        });
+
+        // Contexts are added as the error goes up, so the first one is the
+        // most specific.
+        let mut expected = error
+            .inner()
+            .context()
+            .find_map(|context| match context {
+                StrContext::Expected(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        // The parser stops without complaining when it can't continue an
+        // expression, so the rest of the input is what's unexpected.
+        if expected.is_empty() && unsupported.is_none() {
+            expected.push("connective".to_string());
+            expected.push("end of input".to_string());
+        }
+
+        Self {
+            span: offset..offset + found.as_ref().map_or(0, String::len),
+            expected,
+            found,
+            unsupported,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(symbol) = self.unsupported {
+            return write!(
+                f,
+                "`{}` ({}) isn't supported by this logic",
+                symbol.unicode_str(),
+                symbol.name()
+            );
+        }
+
+        f.write_str("expected ")?;
+        for (i, expected) in self.expected.iter().enumerate() {
+            match i {
+                0 => {}
+                i if i == self.expected.len() - 1 => f.write_str(" or ")?,
+                _ => f.write_str(", ")?,
+            }
+
+            f.write_str(expected)?;
+        }
+
+        match &self.found {
+            Some(found) => write!(f, ", found `{found}`"),
+            None => f.write_str(", found end of input"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
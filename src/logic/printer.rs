@@ -0,0 +1,340 @@
+//! Printing expressions in unicode, ASCII or LaTeX notation with as few
+//! parentheses as possible.
+//!
+//! The unicode and ASCII output parses back (with the same [`Grammar`]) to
+//! the printed expression, as long as the names of its constants are ones
+//! the parser accepts.
+
+use std::fmt;
+
+use super::{Associativity, Grammar, Symbol};
+
+/// The spelling of the symbols when printing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// `¬p ∧ □q`, see [`Symbol::unicode_str`].
+    #[default]
+    Unicode,
+    /// `!p && []q`, see [`Symbol::ascii_str`].
+    Ascii,
+    /// `\neg p \land \Box q`, see [`Symbol::latex`].
+    Latex,
+}
+
+/// The outermost piece of an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Term<'a, E> {
+    Constant(&'a str),
+    Unary(Symbol, &'a E),
+    Binary(Symbol, &'a E, &'a E),
+}
+
+/// Splitting an expression into the pieces it's made of, the opposite of
+/// [`Construct`](super::Construct).
+pub trait Inspect: Sized {
+    fn inspect(&self) -> Term<'_, Self>;
+}
+
+/// Values that can be printed in any [`Notation`], such as expressions and
+/// the nodes of tableaux.
+///
+/// Every [`Inspect`] expression implements it.
+pub trait Print {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result;
+}
+
+impl<E: Inspect> Print for E {
+    fn write_with(&self, printer: &Printer, f: &mut dyn fmt::Write) -> fmt::Result {
+        printer.write_expr(f, self)
+    }
+}
+
+/// Prints expressions in the given notation, with the parentheses needed for
+/// them to parse back with the given grammar and no more.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::logic::{Associativity, Grammar, Notation, Printer, Symbol, modal::Expr};
+///
+/// let expr = "((□p) ∧ q) ∨ ¬(p ⊃ (q ⊃ r))".parse::<Expr>().unwrap();
+///
+/// let printer = Printer::new();
+/// assert_eq!(printer.print(&expr), "□p ∧ q ∨ ¬(p ⊃ q ⊃ r)");
+/// assert_eq!(expr.to_string(), "□p ∧ q ∨ ¬(p ⊃ q ⊃ r)");
+///
+/// let printer = Printer::new().notation(Notation::Ascii);
+/// assert_eq!(printer.print(&expr), "[]p && q || !(p > q > r)");
+///
+/// let printer = Printer::new()
+///     .notation(Notation::Latex)
+///     .grammar(Grammar::new().associativity(Symbol::MatImpl, Associativity::Left));
+/// assert_eq!(
+///     printer.print(&expr),
+///     r"\Box p \land q \lor \neg (p \supset (q \supset r))",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Printer {
+    pub notation: Notation,
+    pub grammar: Grammar,
+}
+
+/// Which operand of a binary connective an expression is.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Printer {
+    /// Unicode notation with the default grammar.
+    pub const fn new() -> Self {
+        Self {
+            notation: Notation::Unicode,
+            grammar: Grammar::new(),
+        }
+    }
+
+    pub const fn notation(self, notation: Notation) -> Self {
+        Self { notation, ..self }
+    }
+
+    pub const fn grammar(self, grammar: Grammar) -> Self {
+        Self { grammar, ..self }
+    }
+
+    pub fn print(&self, value: &impl Print) -> String {
+        let mut output = String::new();
+        self.write(&mut output, value)
+            .expect("writing to a string can't fail");
+        output
+    }
+
+    /// Like [`Self::print`], but writes to `f`, for implementing
+    /// [`Display`](fmt::Display).
+    pub fn write(&self, mut f: &mut (impl fmt::Write + ?Sized), value: &impl Print) -> fmt::Result {
+        value.write_with(self, &mut f)
+    }
+
+    fn write_expr<E: Inspect>(&self, f: &mut dyn fmt::Write, expr: &E) -> fmt::Result {
+        match expr.inspect() {
+            Term::Constant(name) => f.write_str(name),
+            Term::Unary(symbol, operand) => {
+                f.write_str(self.spelling(symbol))?;
+                if self.notation == Notation::Latex {
+                    f.write_char(' ')?;
+                }
+
+                let parens = matches!(operand.inspect(), Term::Binary(..));
+                self.write_parenthesized(f, operand, parens)
+            }
+            Term::Binary(symbol, lhs, rhs) => {
+                self.write_parenthesized(f, lhs, self.needs_parens(symbol, lhs, Side::Left))?;
+                write!(f, " {} ", self.spelling(symbol))?;
+                self.write_parenthesized(f, rhs, self.needs_parens(symbol, rhs, Side::Right))
+            }
+        }
+    }
+
+    fn write_parenthesized<E: Inspect>(
+        &self,
+        f: &mut dyn fmt::Write,
+        expr: &E,
+        parens: bool,
+    ) -> fmt::Result {
+        if parens {
+            f.write_char('(')?;
+            self.write_expr(f, expr)?;
+            f.write_char(')')
+        } else {
+            self.write_expr(f, expr)
+        }
+    }
+
+    /// Whether `operand` needs parentheses on the given side of `symbol`.
+    fn needs_parens<E: Inspect>(&self, symbol: Symbol, operand: &E, side: Side) -> bool {
+        let Term::Binary(inner, ..) = operand.inspect() else {
+            // Unary operators bind tighter than any connective.
+            return false;
+        };
+
+        if inner.precedence() != symbol.precedence() {
+            return inner.precedence() < symbol.precedence();
+        }
+
+        // Only a chain of the same connective groups without parentheses,
+        // and only on the side its associativity allows.
+        !(inner == symbol
+            && match self.grammar.associativity_of(symbol) {
+                Associativity::Left => side == Side::Left,
+                Associativity::Right => side == Side::Right,
+                Associativity::Neither => false,
+            })
+    }
+
+    fn spelling(&self, symbol: Symbol) -> &'static str {
+        match self.notation {
+            Notation::Unicode => symbol.unicode_str(),
+            Notation::Ascii => symbol.ascii_str(),
+            Notation::Latex => symbol.latex(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fmt::Debug, str::FromStr};
+
+    use crate::logic::{Construct, classical, conditional, many_valued, modal, tense};
+
+    const CONNECTIVES: [Symbol; 4] = [Symbol::And, Symbol::Or, Symbol::MatImpl, Symbol::MatEquiv];
+
+    /// Every expression on `p` and `q` up to the given depth.
+    fn exprs<E: Construct + Clone>(unary: &[Symbol], binary: &[Symbol], depth: usize) -> Vec<E> {
+        let mut output = vec![E::constant("p"), E::constant("q")];
+        if depth == 0 {
+            return output;
+        }
+
+        let smaller = exprs::<E>(unary, binary, depth - 1);
+        for &symbol in unary {
+            output.extend(smaller.iter().map(|a| E::unary(symbol, a.clone())));
+        }
+
+        for &symbol in binary {
+            for a in &smaller {
+                for b in &smaller {
+                    output.push(E::binary(symbol, a.clone(), b.clone()));
+                }
+            }
+        }
+
+        output
+    }
+
+    /// The string without the pair of parentheses that opens at `open`.
+    fn without_parens(printed: &str, open: usize) -> String {
+        let mut depth = 0;
+        let (close, _) = printed[open..]
+            .char_indices()
+            .find(|&(_, char)| {
+                depth += match char {
+                    '(' => 1,
+                    ')' => -1,
+                    _ => 0,
+                };
+                depth == 0
+            })
+            .expect("parentheses are balanced");
+        let close = close + open;
+
+        format!(
+            "{}{}{}",
+            &printed[..open],
+            &printed[open + 1..close],
+            &printed[close + 1..]
+        )
+    }
+
+    #[test]
+    fn classical_round_trip() {
+        let grammars = [
+            Associativity::Left,
+            Associativity::Right,
+            Associativity::Neither,
+        ]
+        .map(|associativity| {
+            CONNECTIVES
+                .into_iter()
+                .fold(Grammar::new(), |grammar, symbol| {
+                    grammar.associativity(symbol, associativity)
+                })
+        });
+
+        for expr in exprs::<classical::Expr>(&[Symbol::Not], &CONNECTIVES, 2) {
+            for grammar in grammars {
+                for notation in [Notation::Unicode, Notation::Ascii] {
+                    let printed = Printer::new()
+                        .notation(notation)
+                        .grammar(grammar)
+                        .print(&expr);
+                    assert_eq!(
+                        classical::Expr::parse_with(&printed, &grammar),
+                        Ok(expr.clone()),
+                        "{printed}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks that every expression up to depth 2 parses back from how it's
+    /// printed, both in Unicode and in ASCII.
+    fn round_trip<E>(unary: &[Symbol], binary: &[Symbol])
+    where
+        E: Construct + Clone + Print + FromStr + PartialEq + Debug,
+        E::Err: PartialEq + Debug,
+    {
+        for expr in exprs::<E>(unary, binary, 2) {
+            for notation in [Notation::Unicode, Notation::Ascii] {
+                let printed = Printer::new().notation(notation).print(&expr);
+                assert_eq!(printed.parse(), Ok(expr.clone()), "{printed}");
+            }
+        }
+    }
+
+    #[test]
+    fn modal_round_trip() {
+        let unary = [Symbol::Not, Symbol::Possib, Symbol::Necess];
+        round_trip::<modal::Expr>(&unary, &CONNECTIVES);
+    }
+
+    #[test]
+    fn tense_round_trip() {
+        let unary = [
+            Symbol::Not,
+            Symbol::FutureNecess,
+            Symbol::FuturePossib,
+            Symbol::PastNecess,
+            Symbol::PastPossib,
+        ];
+        round_trip::<tense::Expr>(&unary, &CONNECTIVES);
+    }
+
+    #[test]
+    fn conditional_round_trip() {
+        let binary = [
+            Symbol::And,
+            Symbol::Or,
+            Symbol::MatImpl,
+            Symbol::MatEquiv,
+            Symbol::Conditional,
+        ];
+        round_trip::<conditional::Expr>(&[Symbol::Not], &binary);
+    }
+
+    #[test]
+    fn many_valued_round_trip() {
+        let binary = [
+            Symbol::And,
+            Symbol::Or,
+            Symbol::MatImpl,
+            Symbol::MatEquiv,
+            Symbol::Implication,
+        ];
+        round_trip::<many_valued::Expr>(&[Symbol::Not], &binary);
+    }
+
+    #[test]
+    fn parentheses_are_needed() {
+        for expr in exprs::<classical::Expr>(&[Symbol::Not], &CONNECTIVES, 2) {
+            let printed = expr.to_string();
+            for (open, _) in printed.match_indices('(') {
+                let without = without_parens(&printed, open);
+                assert_ne!(without.parse(), Ok(expr.clone()), "{printed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! The tableau rules of the classical connectives, shared by every logic that
+//! extends classical logic with other operators.
+
+use super::{InferenceRule, Inspect, Symbol, Term};
+
+/// Expressions built from atoms with `¬`, `∧`, `∨`, `⊃` and `≡`, and maybe
+/// some other operators.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::logic::{InferenceRule, Propositional, modal::Expr};
+///
+/// let expr = "¬(p ∧ □q)".parse::<Expr>().unwrap();
+/// assert_eq!(
+///     expr.propositional_inference(),
+///     Some(InferenceRule::split("¬p".parse().unwrap(), "¬□q".parse().unwrap())),
+/// );
+/// assert_eq!(expr.rule_name(), "¬∧");
+///
+/// // `□` has its own rule, but its negation just moves inwards.
+/// let expr = "¬□q".parse::<Expr>().unwrap();
+/// assert_eq!(
+///     expr.propositional_inference(),
+///     Some(InferenceRule::single("◇¬q".parse().unwrap())),
+/// );
+/// assert_eq!("□q".parse::<Expr>().unwrap().propositional_inference(), None);
+/// ```
+pub trait Propositional: Inspect + Clone {
+    /// The atom with the given name.
+    fn atom(name: &str) -> Self;
+
+    /// `¬A`, where `A` is this expression.
+    fn not(&self) -> Self;
+
+    /// For an operator other than the classical connectives, the expression
+    /// that its negation amounts to with the negation moved inwards, such as
+    /// `◇¬A` for `□A`.
+    fn negated(&self) -> Option<Self> {
+        None
+    }
+
+    /// The name of the atom and whether it's true, if the expression is a
+    /// literal (`p` or `¬p`).
+    fn literal(&self) -> Option<(&str, bool)> {
+        match self.inspect() {
+            Term::Constant(name) => Some((name, true)),
+            Term::Unary(Symbol::Not, p) => match p.inspect() {
+                Term::Constant(name) => Some((name, false)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The literal that contradicts this one, if it's a literal (such as `¬p`
+    /// for `p`).
+    fn complement(&self) -> Option<Self> {
+        let (name, value) = self.literal()?;
+        let atom = Self::atom(name);
+        Some(if value { atom.not() } else { atom })
+    }
+
+    /// The inference of the expression, unless it's one of the other
+    /// operators (or the negation of one without [`Self::negated`]), whose
+    /// rules depend on the logic.
+    fn propositional_inference(&self) -> Option<InferenceRule<Self>> {
+        use InferenceRule as IR;
+
+        Some(match self.inspect() {
+            Term::Constant(_) => IR::none(),
+            Term::Unary(Symbol::Not, p) => match p.inspect() {
+                Term::Constant(_) => IR::none(),
+                Term::Unary(Symbol::Not, p) => IR::single(p.clone()),
+                Term::Binary(Symbol::And, p, q) => IR::split(p.not(), q.not()),
+                Term::Binary(Symbol::Or, p, q) => IR::chain(vec![p.not(), q.not()]),
+                Term::Binary(Symbol::MatImpl, p, q) => IR::chain(vec![p.clone(), q.not()]),
+                Term::Binary(Symbol::MatEquiv, p, q) => {
+                    IR::split_and_chain([p.not(), q.clone()], [p.clone(), q.not()])
+                }
+                _ => IR::single(p.negated()?),
+            },
+            Term::Binary(Symbol::And, p, q) => IR::chain(vec![p.clone(), q.clone()]),
+            Term::Binary(Symbol::Or, p, q) => IR::split(p.clone(), q.clone()),
+            Term::Binary(Symbol::MatImpl, p, q) => IR::split(p.not(), q.clone()),
+            Term::Binary(Symbol::MatEquiv, p, q) => {
+                IR::split_and_chain([p.clone(), q.clone()], [p.not(), q.not()])
+            }
+            _ => return None,
+        })
+    }
+
+    /// The name of the tableau rule that applies to this expression, such as
+    /// "¬∧" or "□".
+    fn rule_name(&self) -> &'static str {
+        match self.inspect() {
+            Term::Constant(_) => "",
+            Term::Unary(Symbol::Not, p) => match p.inspect() {
+                Term::Constant(_) => "",
+                Term::Unary(symbol, _) | Term::Binary(symbol, ..) => negated_rule_name(symbol),
+            },
+            Term::Unary(symbol, _) | Term::Binary(symbol, ..) => symbol.unicode_str(),
+        }
+    }
+}
+
+/// The name of the rule for the negation of an expression whose main
+/// operator is `symbol`.
+fn negated_rule_name(symbol: Symbol) -> &'static str {
+    match symbol {
+        Symbol::Not => "¬¬",
+        Symbol::And => "¬∧",
+        Symbol::Or => "¬∨",
+        Symbol::MatImpl => "¬⊃",
+        Symbol::MatEquiv => "¬≡",
+        Symbol::Conditional => "¬≻",
+        Symbol::Implication => "¬→",
+        Symbol::Possib => "¬◇",
+        Symbol::Necess => "¬□",
+        Symbol::FutureNecess => "¬[F]",
+        Symbol::FuturePossib => "¬⟨F⟩",
+        Symbol::PastNecess => "¬[P]",
+        Symbol::PastPossib => "¬⟨P⟩",
+    }
+}
@@ -1,15 +1,328 @@
-use std::{error::Error, str::FromStr};
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Read},
+    process::ExitCode,
+    time::Duration,
+};
 
-use tableaux::logic::{self, Logic, modal};
+use tableaux::{
+    InferenceStatus, LatexPackage, Limits, PartialTableau, Renderer, TableauParseError, examples,
+    logic::{DynLogic, Logic, ParseError},
+};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let tableau = logic::normal_modal::NormalModal::new()
-        .reflexive()
-        .tableau(None, modal::Expr::from_str("□p ⊃ p").unwrap())
-        .infer();
+mod repl;
 
-    println!("{tableau}");
-    assert!(tableau.holds());
+const USAGE: &str = "\
+Usage: tableaux prove [--logic <LOGIC>] [--format <FORMAT>] [--file <PATH>]... [ARGUMENT]...
+       tableaux repl [--logic <LOGIC>]
+       tableaux verify <FILE>...
 
-    Ok(())
+Proves arguments of the form `p ⊃ q, p ⊢ q` (or `p > q, p |- q` in ASCII),
+printing the tableau of each one and a countermodel for every open branch.
+
+Arguments are read from the command line, from the given files, or from
+standard input if there are none (use `-` to read it explicitly), one per line.
+
+Options:
+  -l, --logic <LOGIC>  classical, K, T, D, B, S4, S5, or K followed by frame
+                       conditions such as Kρτ or Krt (but Kτ rather than the
+                       ambiguous Kt), the non-normal N, S2 and S3, the tense
+                       Kₜ (or tense) followed by any of +FC, +BC, +D and +L,
+                       the intuitionist I, the conditional C and C+, or the
+                       many-valued K3, Ł3 (or L3), LP and RM3
+                       [default: classical]
+  -o, --format <FORMAT>
+                       text, forest or prooftrees for LaTeX source of the
+                       tableau with that package, or dot for Graphviz
+                       [default: text]
+  -f, --file <PATH>    Read arguments from a file, one per line
+  -h, --help           Print this help
+
+Text output fits tableaux in $COLUMNS columns (80 if unset) and is coloured
+when printing to a terminal, unless $NO_COLOR is set.
+
+The `repl` command reads arguments and commands such as `:step` from standard
+input, to build tableaux one inference at a time (type `:help` for the list).
+
+The `verify` command checks every example of the given files (such as
+examples-graham-priest.toml) and prints a table with the result of each one.
+
+Exit status:
+  0  every argument holds (or, for `verify`, every example passes)
+  1  some argument doesn't hold (or some example fails)
+  2  some argument couldn't be parsed (or some file is invalid)
+  3  the command line is wrong
+  4  some tableau was too big to finish, and might be infinite";
+
+const EXIT_INVALID: u8 = 1;
+const EXIT_PARSE_ERROR: u8 = 2;
+const EXIT_USAGE: u8 = 3;
+const EXIT_GAVE_UP: u8 = 4;
+
+/// How `prove` prints each tableau.
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Text,
+    Latex(LatexPackage),
+    Dot,
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("prove") => match prove(&args[1..]) {
+            Ok(code) => code,
+            Err(err) => usage_error(&err),
+        },
+        Some("repl") => match repl::repl(&args[1..]) {
+            Ok(code) => code,
+            Err(err) => usage_error(&err),
+        },
+        Some("verify") => match verify(&args[1..]) {
+            Ok(code) => code,
+            Err(err) => usage_error(&err),
+        },
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Some(command) => usage_error(&format!("unknown command `{command}`")),
+        None => usage_error("missing command"),
+    }
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {message}\n\n{USAGE}");
+    ExitCode::from(EXIT_USAGE)
+}
+
+/// Runs the `prove` command, returning an error message if the command line is
+/// wrong.
+fn prove(args: &[String]) -> Result<ExitCode, String> {
+    let mut logic = DynLogic::Classical(Default::default());
+    let mut format = Format::Text;
+    let mut arguments = Vec::new();
+    let mut read_stdin = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-l" | "--logic" => {
+                let name = args.next().ok_or("missing value for `--logic`")?;
+                logic = name.parse().map_err(|err| format!("{err}"))?;
+            }
+            "-o" | "--format" => {
+                format = match args.next().ok_or("missing value for `--format`")?.as_str() {
+                    "text" => Format::Text,
+                    "forest" => Format::Latex(LatexPackage::Forest),
+                    "prooftrees" => Format::Latex(LatexPackage::Prooftrees),
+                    "dot" => Format::Dot,
+                    name => return Err(format!("unknown format `{name}`")),
+                };
+            }
+            "-f" | "--file" => {
+                let path = args.next().ok_or("missing value for `--file`")?;
+                let contents = fs::read_to_string(path)
+                    .map_err(|err| format!("couldn't read `{path}`: {err}"))?;
+                arguments.extend(lines(&contents));
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(ExitCode::SUCCESS);
+            }
+            "-" => read_stdin = true,
+            option if option.starts_with('-') && option.len() > 1 => {
+                return Err(format!("unknown option `{option}`"));
+            }
+            argument => arguments.push(argument.to_string()),
+        }
+    }
+
+    if read_stdin || arguments.is_empty() {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|err| format!("couldn't read standard input: {err}"))?;
+        arguments.extend(lines(&input));
+    }
+
+    // Tableaux of dense or linear tense logics can be infinite, and none of
+    // the finite ones come close to this many worlds.
+    let limits = Limits::new().max_steps(100_000).max_worlds(200);
+
+    let renderer = renderer();
+    let mut exit_code = 0;
+    for (i, argument) in arguments.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let tableau = match PartialTableau::parse_with(logic.clone(), argument, |expr| {
+            logic.parse_expr(expr)
+        }) {
+            Ok(mut tableau) => match tableau.infer_with_limits(&limits) {
+                InferenceStatus::OutOfBudget(limit) => {
+                    eprintln!(
+                        "error: gave up on `{argument}` after reaching the {limit}, its tableau \
+                         might be infinite"
+                    );
+                    exit_code = exit_code.max(EXIT_GAVE_UP);
+                    continue;
+                }
+                _ => tableau.infer(),
+            },
+            Err(err) => {
+                print_parse_error(argument, &err);
+                exit_code = EXIT_PARSE_ERROR;
+                continue;
+            }
+        };
+
+        let verdict = if tableau.holds() {
+            format!("holds in {} logic", logic_name(&logic))
+        } else {
+            exit_code = exit_code.max(EXIT_INVALID);
+            format!("doesn't hold in {} logic", logic_name(&logic))
+        };
+
+        match format {
+            Format::Text => {
+                println!("{argument}");
+                print!("{}", renderer.render(&tableau));
+                println!("{verdict}");
+                for (i, countermodel) in tableau.countermodels().iter().enumerate() {
+                    println!("countermodel {}:\n{countermodel}", i + 1);
+                }
+            }
+            Format::Latex(package) => {
+                println!("% {argument}: {verdict}");
+                print!("{}", tableau.to_latex(package));
+            }
+            Format::Dot => {
+                println!("// {argument}: {verdict}");
+                print!("{}", tableau.to_dot());
+            }
+        }
+    }
+
+    Ok(ExitCode::from(exit_code))
+}
+
+/// Runs the `verify` command, returning an error message if the command line
+/// is wrong.
+fn verify(paths: &[String]) -> Result<ExitCode, String> {
+    if paths.is_empty() {
+        return Err("missing examples file".to_string());
+    }
+
+    // Tableaux of dense or linear tense logics can be infinite, and this also
+    // keeps a regression from hanging the whole suite.
+    let limits = Limits::new().max_steps(100_000).max_worlds(200);
+
+    let mut exit_code = 0;
+    let (mut passed, mut total, mut total_time) = (0, 0, Duration::ZERO);
+    for path in paths {
+        let examples = match fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|source| examples::parse(&source).map_err(|err| err.to_string()))
+        {
+            Ok(examples) => examples,
+            Err(err) => {
+                eprintln!("error: couldn't read `{path}`: {err}");
+                exit_code = EXIT_PARSE_ERROR;
+                continue;
+            }
+        };
+
+        let width = examples
+            .iter()
+            .map(|example| example.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("example".len());
+
+        println!("{path}");
+        println!(
+            "  {:<width$}  {:<9}  {:<12}  {:<24}  {:>10}",
+            "example", "logic", "expected", "result", "time"
+        );
+        for example in &examples {
+            let verification = example.verify(&limits);
+            let result = match &verification.status {
+                Ok(InferenceStatus::Closed) => "holds".to_string(),
+                Ok(InferenceStatus::Open) => "doesn't hold".to_string(),
+                Ok(status) => status.to_string(),
+                Err(err) => format!("parse error: {err}"),
+            };
+
+            let ok = verification.passed(example);
+            println!(
+                "{} {:<width$}  {:<9}  {:<12}  {:<24}  {:>10}",
+                if ok { "✓" } else { "✘" },
+                example.name,
+                logic_name(&example.logic),
+                if example.holds {
+                    "holds"
+                } else {
+                    "doesn't hold"
+                },
+                result,
+                format!("{:.2?}", verification.elapsed),
+            );
+
+            total += 1;
+            total_time += verification.elapsed;
+            if ok {
+                passed += 1;
+            } else {
+                exit_code = exit_code.max(EXIT_INVALID);
+            }
+        }
+
+        println!();
+    }
+
+    println!("{passed}/{total} examples passed in {total_time:.2?}");
+
+    Ok(ExitCode::from(exit_code))
+}
+
+/// Prints the error with a line under the part of the argument it's about.
+fn print_parse_error(argument: &str, err: &TableauParseError<ParseError>) {
+    eprintln!("error: couldn't parse argument, {err}");
+    eprintln!("  {argument}");
+    if let Some(span) = err.span() {
+        let start = argument[..span.start].chars().count();
+        let len = argument[span].chars().count().max(1);
+        eprintln!("  {}{}", " ".repeat(start), "^".repeat(len));
+    }
+}
+
+/// Draws tableaux in $COLUMNS columns, with colours if printing to a
+/// terminal.
+fn renderer() -> Renderer {
+    Renderer::new()
+        .width(
+            env::var("COLUMNS")
+                .ok()
+                .and_then(|columns| columns.parse().ok())
+                .unwrap_or(80),
+        )
+        .color(io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none())
+}
+
+/// Non-empty lines of the input.
+fn lines(input: &str) -> impl Iterator<Item = String> + '_ {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+fn logic_name(logic: &DynLogic) -> String {
+    match logic {
+        DynLogic::Classical(_) => "classical".to_string(),
+        logic => logic.symbol().into_owned(),
+    }
 }
@@ -0,0 +1,348 @@
+//! The `repl` command, for building tableaux one inference at a time.
+
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    mem,
+    process::ExitCode,
+};
+
+use tableaux::{
+    InferenceStatus, Limits, PartialTableau, TableauParseError,
+    logic::{DynLogic, ParseError, normal_modal::NormalModal},
+};
+
+use crate::{logic_name, print_parse_error, renderer};
+
+const HELP: &str = "\
+Enter an argument such as `□p ⊢ p` to start its tableau, or a command:
+  :logic <LOGIC>       Switch logic, restarting the tableau
+  :toggle <CONDITION>  Toggle a frame condition of a modal logic: reflexive,
+                       symmetric, transitive or extendable (or ρ, σ, τ, η),
+                       of which non-normal logics only have the first and
+                       third, or of a tense logic: forward-convergent,
+                       backward-convergent, dense or linear (or FC, BC, D,
+                       L)
+  :step [N]            Infer the next N nodes [default: 1]
+  :infer <NODE>        Infer the given node
+  :run                 Infer until the tableau is finished
+  :show                Print the tableau and the nodes left to infer
+  :countermodels       Print a countermodel for every open branch
+  :undo                Undo the last command
+  :help                Print this help
+  :quit                Quit (or press Ctrl-D)";
+
+/// Everything a command can change, saved before each one for `:undo`.
+#[derive(Debug, Clone)]
+struct State {
+    logic: DynLogic,
+    argument: Option<Argument>,
+}
+
+#[derive(Debug, Clone)]
+struct Argument {
+    text: String,
+    tableau: PartialTableau<DynLogic>,
+    /// Whether there is nothing left to infer.
+    finished: bool,
+}
+
+/// Runs the `repl` command, returning an error message if the command line is
+/// wrong.
+pub fn repl(args: &[String]) -> Result<ExitCode, String> {
+    let mut logic = DynLogic::Classical(Default::default());
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-l" | "--logic" => {
+                let name = args.next().ok_or("missing value for `--logic`")?;
+                logic = name.parse().map_err(|err| format!("{err}"))?;
+            }
+            arg => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    if interactive {
+        println!("Type :help for the list of commands.");
+    }
+
+    let mut state = State {
+        logic,
+        argument: None,
+    };
+    let mut history = Vec::new();
+
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            print!("{}> ", logic_name(&state.logic));
+            io::stdout().flush().map_err(|err| err.to_string())?;
+        }
+
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line.map_err(|err| format!("couldn't read standard input: {err}"))?;
+        let line = line.trim();
+
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match command {
+            "" => continue,
+            ":quit" | ":q" => break,
+            ":help" | ":h" => println!("{HELP}"),
+            ":undo" => match history.pop() {
+                Some(previous) => {
+                    state = previous;
+                    state.show();
+                }
+                None => eprintln!("error: nothing to undo"),
+            },
+            ":show" => state.show(),
+            ":countermodels" => state.countermodels(),
+            command => {
+                let mut next = state.clone();
+                let result = if command.starts_with(':') {
+                    next.apply(command, rest.trim())
+                } else {
+                    next.start(line).map_err(|err| {
+                        print_parse_error(line, &err);
+                        None
+                    })
+                };
+
+                match result {
+                    Ok(()) => {
+                        history.push(mem::replace(&mut state, next));
+                        state.show();
+                    }
+                    Err(Some(err)) => eprintln!("error: {err}"),
+                    Err(None) => {}
+                }
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+impl State {
+    /// Runs a command that changes the state. Errors that were already
+    /// printed are [`None`].
+    fn apply(&mut self, command: &str, rest: &str) -> Result<(), Option<String>> {
+        match command {
+            ":logic" => {
+                self.logic = rest.parse().map_err(|err| format!("{err}"))?;
+                self.restart()
+            }
+            ":toggle" => {
+                self.logic = toggle(&self.logic, rest)?;
+                self.restart()
+            }
+            ":step" => {
+                let steps = match rest {
+                    "" => 1,
+                    steps => steps
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid number of steps `{steps}`"))?,
+                };
+
+                let argument = self.unfinished()?;
+                for _ in 0..steps {
+                    if argument.tableau.infer_once().is_none() {
+                        argument.finished = true;
+                        break;
+                    }
+                }
+
+                Ok(())
+            }
+            ":infer" => {
+                let argument = self.unfinished()?;
+                let node_id = argument
+                    .tableau
+                    .uninferred()
+                    .into_iter()
+                    .find(|node_id| node_id.to_string() == rest)
+                    .ok_or_else(|| format!("node `{rest}` isn't waiting to be inferred"))?;
+
+                argument.tableau.infer_node(node_id);
+                Ok(())
+            }
+            ":run" => {
+                let argument = self.unfinished()?;
+
+                // Tableaux of dense or linear tense logics can be infinite,
+                // and this also keeps a bug from hanging the session.
+                let status = argument
+                    .tableau
+                    .infer_with_limits(&Limits::new().max_steps(100_000).max_worlds(200));
+                if let InferenceStatus::OutOfBudget(limit) = status {
+                    return Err(Some(format!("stopped after reaching the {limit}")));
+                }
+
+                argument.finished = true;
+                Ok(())
+            }
+            command => Err(Some(format!("unknown command `{command}`, try :help"))),
+        }
+    }
+
+    /// Starts the tableau of the given argument.
+    fn start(&mut self, text: &str) -> Result<(), TableauParseError<ParseError>> {
+        let tableau = PartialTableau::parse_with(self.logic.clone(), text, |expr| {
+            self.logic.parse_expr(expr)
+        })?;
+
+        self.argument = Some(Argument {
+            text: text.to_string(),
+            tableau,
+            finished: false,
+        });
+
+        Ok(())
+    }
+
+    /// Starts the tableau of the current argument again, after the logic
+    /// changed.
+    fn restart(&mut self) -> Result<(), Option<String>> {
+        let Some(argument) = self.argument.take() else {
+            return Ok(());
+        };
+
+        self.start(&argument.text).map_err(|err| {
+            print_parse_error(&argument.text, &err);
+            None
+        })
+    }
+
+    /// The current argument, if its tableau can still grow.
+    fn unfinished(&mut self) -> Result<&mut Argument, Option<String>> {
+        match &mut self.argument {
+            None => Err(Some("enter an argument first".to_string())),
+            Some(argument) if argument.is_finished() => {
+                Err(Some("the tableau is already finished".to_string()))
+            }
+            Some(argument) => Ok(argument),
+        }
+    }
+
+    fn show(&self) {
+        let name = logic_name(&self.logic);
+        let Some(argument) = &self.argument else {
+            println!("Using {name} logic.");
+            return;
+        };
+
+        println!("{} ({name})", argument.text);
+        print!("{}", renderer().render_partial(&argument.tableau));
+
+        if argument.tableau.live_leaves().is_empty() {
+            println!("Every branch is closed, so the argument holds in {name} logic.");
+        } else if argument.is_finished() {
+            println!("The argument doesn't hold in {name} logic, see :countermodels.");
+        } else {
+            let uninferred = argument
+                .tableau
+                .uninferred()
+                .iter()
+                .map(|node_id| node_id.to_string())
+                .collect::<Vec<_>>();
+            if uninferred.is_empty() {
+                println!("Nothing is left to infer, but :step may extend open branches.");
+            } else {
+                println!("Left to infer: {}", uninferred.join(", "));
+            }
+        }
+    }
+
+    fn countermodels(&self) {
+        match &self.argument {
+            None => eprintln!("error: enter an argument first"),
+            Some(argument) if !argument.is_finished() => {
+                eprintln!("error: the tableau isn't finished, :run finishes it");
+            }
+            Some(argument) => {
+                let tableau = argument.tableau.clone().infer();
+                if tableau.holds() {
+                    println!("The argument holds, so there are no countermodels.");
+                }
+
+                for (i, countermodel) in tableau.countermodels().iter().enumerate() {
+                    println!("countermodel {}:\n{countermodel}", i + 1);
+                }
+            }
+        }
+    }
+}
+
+impl Argument {
+    /// Whether inference is over, either because there is nothing left to
+    /// infer or because every branch is closed.
+    fn is_finished(&self) -> bool {
+        self.finished || self.tableau.live_leaves().is_empty()
+    }
+}
+
+/// The logic with the given frame condition switched on or off.
+fn toggle(logic: &DynLogic, condition: &str) -> Result<DynLogic, String> {
+    if let DynLogic::NonNormal(logic) = logic {
+        let mut logic = *logic;
+        let condition = match condition {
+            "reflexive" | "ρ" => &mut logic.reflexive,
+            "transitive" | "τ" => &mut logic.transitive,
+            "" => return Err("missing frame condition".to_string()),
+            condition => {
+                return Err(format!(
+                    "unknown frame condition `{condition}` for non-normal logics"
+                ));
+            }
+        };
+        *condition = !*condition;
+
+        return Ok(DynLogic::NonNormal(logic));
+    }
+
+    if let DynLogic::Tense(logic) = logic {
+        let mut logic = *logic;
+        let condition = match condition {
+            "forward-convergent" | "FC" => &mut logic.forward_convergent,
+            "backward-convergent" | "BC" => &mut logic.backward_convergent,
+            "dense" | "D" => &mut logic.dense,
+            "linear" | "L" => &mut logic.linear,
+            "" => return Err("missing frame condition".to_string()),
+            condition => {
+                return Err(format!(
+                    "unknown frame condition `{condition}` for tense logics"
+                ));
+            }
+        };
+        *condition = !*condition;
+
+        return Ok(DynLogic::Tense(logic));
+    }
+
+    let mut modal = match logic {
+        DynLogic::Modal(_) => NormalModal::new(),
+        DynLogic::NormalModal(logic) => *logic,
+        logic => {
+            return Err(format!(
+                "{} logic has no frame conditions",
+                logic_name(logic)
+            ));
+        }
+    };
+
+    let condition = match condition {
+        "reflexive" | "ρ" => &mut modal.reflexive,
+        "symmetric" | "σ" => &mut modal.symmetric,
+        "transitive" | "τ" => &mut modal.transitive,
+        "extendable" | "η" => &mut modal.extendable,
+        "" => return Err("missing frame condition".to_string()),
+        condition => return Err(format!("unknown frame condition `{condition}`")),
+    };
+    *condition = !*condition;
+
+    Ok(DynLogic::NormalModal(modal))
+}
@@ -1,7 +1,23 @@
 use core::fmt;
-use std::{cmp, collections::BinaryHeap, error::Error, marker::PhantomData, str::FromStr};
-
-use crate::Logic;
+use std::{borrow::Cow, cmp, collections::BinaryHeap, marker::PhantomData, str::FromStr};
+
+use crate::{
+    Logic,
+    logic::{InferenceRule, Place, modal::World},
+};
+
+mod dot;
+mod index;
+mod latex;
+mod limits;
+mod render;
+#[cfg(feature = "serde")]
+mod serialize;
+pub(crate) use dot::escape as dot_escape;
+use index::{BranchIndex, Trie, WorldKey};
+pub use latex::LatexPackage;
+pub use limits::{InferenceStatus, Limit, Limits};
+pub use render::Renderer;
 
 #[derive(Debug, Clone)]
 pub struct PartialTableau<L: Logic> {
@@ -9,8 +25,16 @@ pub struct PartialTableau<L: Logic> {
     pub logic: L,
     nodes: Vec<TableauNode<L::Node>>,
     pub(crate) root: NodeId,
+    /// Nodes of each branch, see [`TableauNode::trie`].
+    index: BranchIndex,
     /// Non-terminal nodes that need to be expanded.
     uninferred_nodes: BinaryHeap<NodeIdPriority>,
+    /// Number of inference steps carried out so far.
+    steps: u32,
+    /// Depth of the deepest node so far.
+    max_depth: usize,
+    /// Number of worlds used so far, see [`Logic::worlds_in`].
+    worlds: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,13 +44,49 @@ pub struct TableauNode<V> {
     // TODO: Use a smallvec type
     pub(crate) children: Vec<NodeId>,
     pub(crate) live_children: u8,
-    // TODO: Add death reason.
-    pub(crate) death_reason: Option<()>,
+    pub(crate) death_reason: Option<DeathReason>,
+    /// Where the node comes from. [`None`] for premises, the conclusion and
+    /// nodes added by [`Logic::initialize`].
+    pub(crate) justification: Option<Justification>,
+    /// Number of ancestors of the node.
+    depth: u32,
+    /// Every node in the branch that ends in this node, including itself.
+    trie: Trie,
+    /// Number of worlds in the branch that ends in this node, see
+    /// [`Logic::worlds_in`].
+    worlds: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct NodeId {
-    pub(crate) index: u16,
+    pub(crate) index: u32,
+}
+
+/// Why a node (and therefore every branch that goes through it) is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeathReason {
+    /// The node contradicts the node at `ancestor`, which is higher up in the
+    /// same branch (for example, `p, 0` and `¬p, 0`).
+    Contradiction { ancestor: NodeId },
+    /// Every branch that goes through the node's children is closed.
+    ChildrenDied,
+}
+
+/// Records how an inferred node was obtained, such as "from 3 by ¬⊃".
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Justification {
+    /// The node whose inference produced this one.
+    pub source: NodeId,
+    /// The shape of the inference that produced this node.
+    pub rule: InferenceRule<()>,
+    /// The name of the rule that fired, such as "¬∧" or "□".
+    pub name: Cow<'static, str>,
+    /// The inference step (starting from 0) in which the node was added.
+    pub step: u32,
 }
 
 /// A [`NodeId`] that has some priority, used
@@ -45,7 +105,11 @@ impl<L: Logic> PartialTableau<L> {
             logic,
             nodes: Vec::with_capacity(premises.size_hint().0 + 1),
             root: NodeId { index: 0 },
+            index: BranchIndex::default(),
             uninferred_nodes: BinaryHeap::with_capacity(premises.size_hint().0 + 1),
+            steps: 0,
+            max_depth: 0,
+            worlds: 0,
         };
 
         for premise in premises {
@@ -54,13 +118,21 @@ impl<L: Logic> PartialTableau<L> {
 
         tableau.add_orphan(tableau.logic.make_conclusion_node(conclusion));
 
+        // The root doesn't get bound to anything, so it has to be indexed by
+        // hand.
+        tableau.index_node(tableau.root, None);
+
         for i in 0..tableau.nodes.len() - 1 {
-            tableau.bind_child(
-                NodeId { index: i as u16 },
-                NodeId {
-                    index: i as u16 + 1,
-                },
-            );
+            tableau.bind_child(NodeId::from_index(i), NodeId::from_index(i + 1));
+        }
+
+        // The premises and conclusion might already contradict each other.
+        for i in 0..tableau.nodes.len() {
+            let node_id = NodeId::from_index(i);
+            if tableau.check_branch_liveness(node_id) {
+                tableau.propagate_branch_liveness(node_id);
+                break;
+            }
         }
 
         L::initialize(&mut tableau);
@@ -71,6 +143,7 @@ impl<L: Logic> PartialTableau<L> {
 
 impl<L: Logic> Tableau<L> {
     /// Same as [`PartialTableau::new`]
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         logic: L,
         premises: impl IntoIterator<Item = L::Expr>,
@@ -100,9 +173,38 @@ impl<L: Logic> Tableau<L> {
     /// let tableau = logic::modal::infer("p ⊃ q ⊢ q ⊃ p");
     /// assert_eq!(tableau.holds(), false);
     /// ```
+    ///
+    /// A split doesn't close just because one of its sides is already in the
+    /// branch:
+    ///
+    /// ```rust
+    /// use tableaux::logic;
+    ///
+    /// assert!(!logic::classical::infer("p, p ∨ q ⊢ q").holds());
+    /// assert!(!logic::classical::infer("p, p ≡ q ⊢ r").holds());
+    /// ```
     pub fn holds(&self) -> bool {
         self.get(self.root).is_dead()
     }
+
+    /// Why the given node is closed, or [`None`] if it's still open.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::{DeathReason, logic};
+    ///
+    /// let tableau = logic::classical::infer("p ⊢ p");
+    /// let leaf = tableau.children(tableau.root())[0];
+    /// assert!(matches!(
+    ///     tableau.death_reason(leaf),
+    ///     Some(DeathReason::Contradiction { .. })
+    /// ));
+    /// assert_eq!(tableau.death_reason(tableau.root()), Some(DeathReason::ChildrenDied));
+    /// ```
+    pub fn death_reason(&self, node_id: NodeId) -> Option<DeathReason> {
+        self.get(node_id).death_reason
+    }
 }
 
 impl<L: Logic> PartialTableau<L>
@@ -116,158 +218,225 @@ where
 
         debug_assert!(self.uninferred_nodes.is_empty());
         Tableau {
+            logic: self.logic,
             nodes: self.nodes,
             root: self.root,
+            index: self.index,
         }
     }
 
-    /// Adds the inference result of a node to the tableau.
+    /// Adds the inference result of a node to the tableau, taking it out of
+    /// the nodes waiting to be inferred.
     ///
     /// Generally you should use [`Self::infer_once`] instead.
     pub fn infer_node(&mut self, node: NodeId) -> Option<()> {
+        self.uninferred_nodes
+            .retain(|priority| priority.node_id != node);
+        self.expand(node)
+    }
+
+    /// Adds the inference result of a node that is no longer waiting to be
+    /// inferred.
+    fn expand(&mut self, node: NodeId) -> Option<()> {
         let initial_node_len = self.nodes.len();
 
-        for leaf in self.live_leaves() {
+        // Only the branches that go through the node can use it.
+        for leaf in live_leaves(&self.nodes, node) {
             let branch = self.branch(leaf);
+            let inference = self.logic.infer(&self.get(node).value, branch);
+            let justification = Justification {
+                source: node,
+                rule: inference.as_ref().map(|_| ()),
+                name: self
+                    .logic
+                    .rule_name(&self.get(node).value, inference.as_ref()),
+                step: self.steps,
+            };
 
-            // NOTE: In all cases, we have to check branch liveness at the end
-            // in splits because we need to add both before killing other
-            // branches, but we need to check on the loop in chains to make
-            // sure we don't expand extra nodes if it's dead.
-            use crate::logic::InferenceRule as IR;
-            match self.logic.infer(&self.get(node).value, branch) {
-                IR::None => (),
-                IR::Single(node) => {
-                    if let Some(new_node) = self.add_child(leaf, node) {
-                        self.check_branch_liveness(new_node);
-                    }
-                }
-                IR::Split([left, right]) => {
-                    if let Some(new_left) = self.add_child(leaf, left) {
-                        self.check_branch_liveness(new_left);
-                    }
-
-                    if let Some(new_right) = self.add_child(leaf, right) {
-                        self.check_branch_liveness(new_right);
-                    }
-                }
-                IR::Chain(nodes) => {
-                    let mut leaf = leaf;
-                    for node in nodes {
-                        if let Some(new_node) = self.add_child(leaf, node) {
-                            let died = self.check_branch_liveness(new_node);
-                            if died {
-                                break;
-                            }
-
-                            leaf = new_node;
-                        }
-                    }
-                }
-                IR::SplitAndChain(chains) => {
-                    for [a, b] in chains {
-                        if let Some(new_a) = self.add_child(leaf, a) {
-                            let died = self.check_branch_liveness(new_a);
-                            if !died {
-                                if let Some(new_b) = self.add_child(new_a, b) {
-                                    self.check_branch_liveness(new_b);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            self.add_inference(leaf, inference, &justification);
         }
 
-        // NOTE: This depends on the implementation of `Self::add_orphan`.
-        // Thankfully it's pretty logical but just watch out if that tries to
-        // be optimized.
-        for i in initial_node_len..self.nodes.len() {
-            let node_id = NodeId { index: i as u16 };
-            self.propagate_branch_liveness(node_id);
-        }
+        self.finish_step(initial_node_len);
 
         Some(())
     }
 
-    /// Infers the first prioritized uninferred node.
+    /// Infers the first prioritized uninferred node. If there are none left,
+    /// it tries to extend the saturated branches (see [`Logic::saturate`]).
     ///
     /// Returns `Some(())` if an inference was made, `None` otherwise.
     ///
     /// See also [`Self::infer`].
     pub fn infer_once(&mut self) -> Option<()> {
-        let node_id = self.uninferred_nodes.pop()?.node_id;
-        self.infer_node(node_id)
+        match self.uninferred_nodes.pop() {
+            Some(NodeIdPriority { node_id, .. }) => self.expand(node_id),
+            None => self.saturate(),
+        }
     }
 
-    fn check_branch_liveness(&mut self, leaf: NodeId) -> bool {
-        if self.logic.has_contradiction(self.branch(leaf)) {
-            self.get_mut(leaf).death_reason = Some(());
-            true
-        } else {
-            false
+    /// Applies [`Logic::saturate`] to every live branch.
+    ///
+    /// Returns `Some(())` if any node was added, `None` otherwise.
+    fn saturate(&mut self) -> Option<()> {
+        let initial_node_len = self.nodes.len();
+
+        for leaf in self.live_leaves() {
+            let inference = self.logic.saturate(self.branch(leaf));
+            let justification = Justification {
+                source: leaf,
+                rule: inference.as_ref().map(|_| ()),
+                name: self
+                    .logic
+                    .rule_name(&self.get(leaf).value, inference.as_ref()),
+                step: self.steps,
+            };
+
+            self.add_inference(leaf, inference, &justification);
         }
-    }
 
-    fn propagate_branch_liveness(&mut self, node_id: NodeId) {
-        if !self.get(node_id).is_dead() {
-            return;
+        if self.nodes.len() == initial_node_len {
+            return None;
         }
 
-        let Some(parent) = self.get(node_id).parent else {
-            return;
-        };
+        self.finish_step(initial_node_len);
 
-        self.get_mut(parent).live_children -= 1;
-        if self.get(parent).live_children == 0 {
-            self.get_mut(parent).death_reason = Some(());
-            self.propagate_branch_liveness(parent);
-        }
+        Some(())
     }
-}
 
-pub struct Tableau<L: Logic> {
-    nodes: Vec<TableauNode<L::Node>>,
-    root: NodeId,
-}
+    /// Adds the nodes of an inference below the given leaf.
+    fn add_inference(
+        &mut self,
+        leaf: NodeId,
+        inference: InferenceRule<L::Node>,
+        justification: &Justification,
+    ) {
+        // NOTE: In all cases, we have to check branch liveness at the end
+        // in splits because we need to add both before killing other
+        // branches, but we need to check on the loop in chains to make
+        // sure we don't expand extra nodes if it's dead.
+        use crate::logic::InferenceRule as IR;
+        match inference {
+            IR::None => (),
+            IR::Single(node) => {
+                if let Some(new_node) = self.add_inferred_child(leaf, node, justification) {
+                    self.check_branch_liveness(new_node);
+                }
+            }
+            IR::Split([left, right]) => {
+                self.add_split(leaf, vec![vec![left], vec![right]], justification);
+            }
+            IR::Chain(nodes) => {
+                let mut leaf = leaf;
+                for node in nodes {
+                    if let Some(new_node) = self.add_inferred_child(leaf, node, justification) {
+                        let died = self.check_branch_liveness(new_node);
+                        if died {
+                            break;
+                        }
 
-impl<L: Logic> Tableau<L> {
-    pub fn countermodel(&self) -> Option<Countermodel<L::Node>> {
-        if self.holds() {
-            return None;
+                        leaf = new_node;
+                    }
+                }
+            }
+            IR::SplitAndChain([left, right]) => {
+                self.add_split(leaf, vec![left.into(), right.into()], justification);
+            }
+            IR::SplitChains(chains) => self.add_split(leaf, chains, justification),
         }
+    }
 
-        let mut nodes = Vec::new();
-        let mut stack = vec![self.root];
+    /// Adds one branch below the given leaf for each chain of nodes.
+    fn add_split(
+        &mut self,
+        leaf: NodeId,
+        chains: Vec<Vec<L::Node>>,
+        justification: &Justification,
+    ) {
+        // The branch already is one of the options, so splitting would only
+        // drop it.
+        let branch = self.branch(leaf);
+        let already = chains
+            .iter()
+            .any(|chain| chain.iter().all(|node| branch.contains(node)));
+        drop(branch);
+        if already {
+            return;
+        }
 
-        while let Some(node_id) = stack.pop() {
-            if self.get(node_id).is_dead() {
-                continue;
+        for chain in chains {
+            let mut leaf = leaf;
+            for node in chain {
+                if let Some(new_node) = self.add_inferred_child(leaf, node, justification) {
+                    if self.check_branch_liveness(new_node) {
+                        break;
+                    }
+
+                    leaf = new_node;
+                }
             }
+        }
+    }
 
-            nodes.push(self.get(node_id).value.clone());
-            stack.extend(&self.get(node_id).children);
+    /// Propagates the deaths of the nodes added in this step and moves on to
+    /// the next one.
+    fn finish_step(&mut self, initial_node_len: usize) {
+        // NOTE: This depends on the implementation of `Self::add_orphan`.
+        // Thankfully it's pretty logical but just watch out if that tries to
+        // be optimized.
+        for i in initial_node_len..self.nodes.len() {
+            let node_id = NodeId::from_index(i);
+            self.propagate_branch_liveness(node_id);
         }
 
-        Some(Countermodel { nodes })
+        self.steps += 1;
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Countermodel<N> {
-    nodes: Vec<N>,
+pub struct Tableau<L: Logic> {
+    /// The underlying logic system of the tableau.
+    pub logic: L,
+    nodes: Vec<TableauNode<L::Node>>,
+    root: NodeId,
+    index: BranchIndex,
 }
 
-impl<N: fmt::Debug + fmt::Display> Error for Countermodel<N> {}
+impl<L: Logic> Tableau<L> {
+    /// One countermodel for each open branch of the tableau.
+    ///
+    /// Empty if the tableau closes, i.e., if the argument holds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic;
+    ///
+    /// let tableau = logic::classical::infer("p ∨ q ⊢ p");
+    /// let countermodels = tableau.countermodels();
+    ///
+    /// // Only the `q` branch stays open.
+    /// assert_eq!(countermodels.len(), 1);
+    /// assert_eq!(countermodels[0].valuation.get("p"), Some(&false));
+    /// assert_eq!(countermodels[0].valuation.get("q"), Some(&true));
+    /// ```
+    pub fn countermodels(&self) -> Vec<L::Countermodel> {
+        self.open_leaves()
+            .into_iter()
+            .map(|leaf| self.logic.countermodel(self.branch(leaf)))
+            .collect()
+    }
+
+    /// Iter over every leaf node that is not dead.
+    pub fn open_leaves(&self) -> Vec<NodeId> {
+        live_leaves(&self.nodes, self.root)
+    }
 
-impl<N: fmt::Display> fmt::Display for Countermodel<N> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "Countermodel: ")?;
-        for node in &self.nodes {
-            writeln!(f, "➡ {node}")?;
+    pub fn branch(&self, leaf: NodeId) -> impl Branch<L> {
+        SimpleBranch {
+            leaf,
+            logic: &self.logic,
+            nodes: &self.nodes,
+            index: &self.index,
         }
-
-        Ok(())
     }
 }
 
@@ -282,32 +451,66 @@ impl<L: Logic> PartialTableau<L> {
         &mut self.nodes[node_id.index as usize]
     }
 
-    pub fn depth_of(&self, node_id: NodeId) -> u16 {
-        self.get(node_id)
-            .children
-            .iter()
-            .map(|child| self.depth_of(*child) + 1)
-            .max()
-            .unwrap_or(0)
+    fn check_branch_liveness(&mut self, leaf: NodeId) -> bool {
+        if let Some(ancestor) = self.logic.has_contradiction(self.branch(leaf)) {
+            self.get_mut(leaf).death_reason = Some(DeathReason::Contradiction { ancestor });
+            true
+        } else {
+            false
+        }
+    }
+
+    fn propagate_branch_liveness(&mut self, mut node_id: NodeId) {
+        if !self.get(node_id).is_dead() {
+            return;
+        }
+
+        while let Some(parent) = self.get(node_id).parent {
+            self.get_mut(parent).live_children -= 1;
+            if self.get(parent).live_children != 0 {
+                break;
+            }
+            self.get_mut(parent).death_reason = Some(DeathReason::ChildrenDied);
+            node_id = parent;
+        }
     }
 
-    pub fn depth(&self) -> u16 {
+    pub fn depth_of(&self, node_id: NodeId) -> u32 {
+        let mut depth = 0;
+        let mut stack = vec![(node_id, 0)];
+        while let Some((node_id, node_depth)) = stack.pop() {
+            depth = depth.max(node_depth);
+            stack.extend(
+                self.get(node_id)
+                    .children
+                    .iter()
+                    .map(|child| (*child, node_depth + 1)),
+            );
+        }
+
+        depth
+    }
+
+    pub fn depth(&self) -> u32 {
         self.depth_of(self.root)
     }
 
     fn add_orphan(&mut self, node_value: L::Node) -> NodeId {
         let priority = self.logic.priority(&node_value);
+        self.worlds = self.worlds.max(self.logic.worlds_in(&node_value));
         let node = TableauNode {
             value: node_value,
             parent: None,
             children: Vec::new(),
             live_children: 0,
             death_reason: None,
+            justification: None,
+            depth: 0,
+            trie: Trie::EMPTY,
+            worlds: 0,
         };
 
-        let node_id = NodeId {
-            index: self.nodes.len() as u16,
-        };
+        let node_id = NodeId::from_index(self.nodes.len());
         self.nodes.push(node);
         self.uninferred_nodes
             .push(NodeIdPriority { node_id, priority });
@@ -321,9 +524,41 @@ impl<L: Logic> PartialTableau<L> {
     fn bind_child(&mut self, parent: NodeId, child: NodeId) {
         let old = self.get_mut(child).parent.replace(parent);
         assert!(old.is_none());
-        let parent = self.get_mut(parent);
-        parent.children.push(child);
-        parent.live_children += 1;
+        let parent_node = self.get_mut(parent);
+        parent_node.children.push(child);
+        parent_node.live_children += 1;
+        let depth = parent_node.depth + 1;
+
+        self.get_mut(child).depth = depth;
+        self.index_node(child, Some(parent));
+        self.max_depth = self.max_depth.max(depth as usize);
+    }
+
+    /// Adds the node to the index of the branch that ends in its parent, by
+    /// its hash and by its place among the worlds, and counts its worlds.
+    fn index_node(&mut self, node_id: NodeId, parent: Option<NodeId>) {
+        let (mut trie, worlds) = match parent {
+            Some(parent) => (self.get(parent).trie, self.get(parent).worlds),
+            None => (Trie::EMPTY, 0),
+        };
+
+        let value = &self.nodes[node_id.index as usize].value;
+        let keys = match self.logic.place(value) {
+            Some(Place::At(world)) => [Some(WorldKey::At(world)), None],
+            Some(Place::Relation { from, to }) => {
+                [Some(WorldKey::From(from)), Some(WorldKey::To(to))]
+            }
+            None => [None, None],
+        };
+        let hashes = keys.iter().flatten().map(BranchIndex::hash);
+        for hash in std::iter::once(BranchIndex::hash(value)).chain(hashes) {
+            trie = self.index.insert(trie, hash, node_id);
+        }
+
+        let worlds = worlds.max(self.logic.worlds_in(value));
+        let node = self.get_mut(node_id);
+        node.trie = trie;
+        node.worlds = worlds;
     }
 
     pub fn add_child(&mut self, parent: NodeId, child: L::Node) -> Option<NodeId>
@@ -336,32 +571,94 @@ impl<L: Logic> PartialTableau<L> {
 
         let child_id = self.add_orphan(child);
         self.bind_child(parent, child_id);
-        return Some(child_id);
+        self.reschedule_ancestors(child_id);
+        Some(child_id)
+    }
+
+    /// Schedules again the ancestors of the node whose inference might be
+    /// different now that the node is in the branch, such as `□A, i` when
+    /// `i r j` gets added.
+    ///
+    /// See [`Logic::reinfer_on`].
+    fn reschedule_ancestors(&mut self, node_id: NodeId) {
+        if !self.logic.reinfers() {
+            return;
+        }
+
+        let new_node = &self.get(node_id).value;
+        let rescheduled = AncestorIter {
+            nodes: &self.nodes,
+            current: node_id,
+        }
+        .filter(|(_, ancestor)| self.logic.reinfer_on(ancestor, new_node))
+        .map(|(ancestor_id, ancestor)| NodeIdPriority {
+            node_id: ancestor_id,
+            priority: self.logic.priority(ancestor),
+        })
+        .collect::<Vec<_>>();
+
+        self.uninferred_nodes.extend(rescheduled);
+    }
+
+    /// Same as [`Self::add_child`], but records where the child comes from.
+    fn add_inferred_child(
+        &mut self,
+        parent: NodeId,
+        child: L::Node,
+        justification: &Justification,
+    ) -> Option<NodeId>
+    where
+        L::Node: Eq,
+    {
+        let child_id = self.add_child(parent, child)?;
+        self.get_mut(child_id).justification = Some(justification.clone());
+        Some(child_id)
     }
 
     pub fn branch(&self, leaf: NodeId) -> impl Branch<L> {
         SimpleBranch {
             leaf,
-            tableau: self,
+            logic: &self.logic,
+            nodes: &self.nodes,
+            index: &self.index,
         }
     }
 
     /// Iter over every leaf node that is not dead.
     pub fn live_leaves(&self) -> Vec<NodeId> {
-        let mut queue: Vec<NodeId> = vec![self.root];
-        let mut output = Vec::new();
-        while let Some(node_id) = queue.pop() {
-            // Skip dead branches
-            if self.get(node_id).is_dead() {
-                continue;
-            }
+        live_leaves(&self.nodes, self.root)
+    }
 
-            // Add if leaf, enqueue rest otherwise
-            let children = &self.get(node_id).children;
-            if children.is_empty() {
-                output.push(node_id);
-            } else {
-                queue.extend(children.into_iter())
+    /// Nodes waiting to be inferred, in the order [`Self::infer_once`] picks
+    /// them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::{PartialTableau, logic::classical::Classical};
+    ///
+    /// let mut tableau = "p ∨ q, p ∧ q ⊢ p ∧ q".parse::<PartialTableau<Classical>>().unwrap();
+    /// let [first, second, third] = tableau.uninferred()[..] else {
+    ///     panic!("expected three nodes");
+    /// };
+    ///
+    /// // The conjunction comes first since it doesn't split the branch, then
+    /// // the oldest node.
+    /// assert_eq!([first, second, third].map(u32::from), [1, 0, 2]);
+    ///
+    /// tableau.infer_node(third);
+    /// assert!(!tableau.uninferred().contains(&third));
+    /// ```
+    pub fn uninferred(&self) -> Vec<NodeId> {
+        let mut uninferred = self.uninferred_nodes.clone().into_sorted_vec();
+        uninferred.reverse();
+
+        // Some nodes (such as `□A, i`) wait to be inferred again for new
+        // worlds, but they're only listed once.
+        let mut output = Vec::with_capacity(uninferred.len());
+        for priority in uninferred {
+            if !output.contains(&priority.node_id) {
+                output.push(priority.node_id);
             }
         }
 
@@ -376,7 +673,7 @@ impl<L: Logic> PartialTableau<L> {
         map_logic: impl FnOnce(&L) -> L2,
         mut map_node: impl FnMut(&L::Node) -> L2::Node,
     ) -> PartialTableau<L2> {
-        PartialTableau {
+        let mut tableau = PartialTableau {
             logic: map_logic(&self.logic),
             nodes: self
                 .nodes
@@ -387,11 +684,31 @@ impl<L: Logic> PartialTableau<L> {
                     children: node.children.clone(),
                     live_children: node.live_children,
                     death_reason: node.death_reason,
+                    justification: node.justification.clone(),
+                    depth: node.depth,
+                    trie: Trie::EMPTY,
+                    worlds: 0,
                 })
                 .collect(),
             root: self.root,
+            index: BranchIndex::default(),
             uninferred_nodes: self.uninferred_nodes.clone(),
+            steps: self.steps,
+            max_depth: self.max_depth,
+            worlds: self.worlds,
+        };
+
+        // The new nodes might hash differently, so the index is rebuilt.
+        // Parents always come before their children.
+        for i in 0..tableau.nodes.len() {
+            let node_id = NodeId::from_index(i);
+            let parent = tableau.get(node_id).parent;
+            if parent.is_some() || node_id == tableau.root {
+                tableau.index_node(node_id, parent);
+            }
         }
+
+        tableau
     }
 }
 
@@ -399,6 +716,56 @@ impl<L: Logic> Tableau<L> {
     fn get(&self, node_id: NodeId) -> &TableauNode<L::Node> {
         &self.nodes[node_id.index as usize]
     }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    pub fn children(&self, node_id: NodeId) -> &[NodeId] {
+        &self.get(node_id).children
+    }
+
+    /// Where the given node comes from, or [`None`] if it is a premise or the
+    /// negated conclusion.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic;
+    ///
+    /// let tableau = logic::classical::infer("⊢ p ⊃ p");
+    /// let root = tableau.root();
+    /// let child = tableau.children(root)[0];
+    /// let justification = tableau.justification(child).unwrap();
+    /// assert_eq!(justification.source, root);
+    /// assert_eq!(justification.name, "¬⊃");
+    /// assert_eq!(justification.to_string(), "from 0 by ¬⊃");
+    /// ```
+    pub fn justification(&self, node_id: NodeId) -> Option<&Justification> {
+        self.get(node_id).justification.as_ref()
+    }
+}
+
+fn live_leaves<V>(nodes: &[TableauNode<V>], root: NodeId) -> Vec<NodeId> {
+    let mut queue: Vec<NodeId> = vec![root];
+    let mut output = Vec::new();
+    while let Some(node_id) = queue.pop() {
+        let node = &nodes[node_id.index as usize];
+
+        // Skip dead branches
+        if node.is_dead() {
+            continue;
+        }
+
+        // Add if leaf, enqueue rest otherwise
+        if node.children.is_empty() {
+            output.push(node_id);
+        } else {
+            queue.extend(&node.children)
+        }
+    }
+
+    output
 }
 
 impl<V> TableauNode<V> {
@@ -412,21 +779,67 @@ pub trait Branch<L: Logic> {
 
     /// Iterator over all nodes in this branch, including the leaf.
     fn ancestors<'t>(&'t self) -> impl Iterator<Item = &'t L::Node>
+    where
+        L::Node: 't,
+    {
+        self.ancestors_with_id().map(|(_, node)| node)
+    }
+
+    /// Same as [`Self::ancestors`], but also yields the id of each node.
+    fn ancestors_with_id<'t>(&'t self) -> impl Iterator<Item = (NodeId, &'t L::Node)>
+    where
+        L::Node: 't;
+
+    /// Nodes in this branch with the given hash, which is what makes
+    /// [`Self::position`] fast.
+    ///
+    /// The hash is the one of the node as indexed by the tableau, so wrapper
+    /// nodes (such as [`DynNode`](crate::logic::DynNode)) must hash the same
+    /// as the node they wrap.
+    fn with_hash<'t>(&'t self, hash: u64) -> impl Iterator<Item = (NodeId, &'t L::Node)>
+    where
+        L::Node: 't;
+
+    /// Number of worlds in this branch, see [`Logic::worlds_in`].
+    fn worlds(&self) -> usize;
+
+    /// A world that doesn't appear anywhere in this branch yet.
+    fn fresh_world(&self) -> World {
+        World::new(u32::try_from(self.worlds()).expect("ran out of worlds"))
+    }
+
+    /// Nodes in this branch at the given world, see [`Logic::place`].
+    fn at_world<'t>(&'t self, world: World) -> impl Iterator<Item = &'t L::Node>
+    where
+        L::Node: 't;
+
+    /// Relations in this branch from the given world, see [`Logic::place`].
+    fn relations_from<'t>(&'t self, world: World) -> impl Iterator<Item = &'t L::Node>
     where
         L::Node: 't;
 
+    /// Relations in this branch to the given world, see [`Logic::place`].
+    fn relations_to<'t>(&'t self, world: World) -> impl Iterator<Item = &'t L::Node>
+    where
+        L::Node: 't;
+
+    /// The id of the given node in this branch, if it's there.
+    ///
+    /// Unlike searching through [`Self::ancestors_with_id`], this doesn't
+    /// walk the whole branch.
+    fn position(&self, node: &L::Node) -> Option<NodeId> {
+        self.with_hash(BranchIndex::hash(node))
+            .find(|(_, other)| *other == node)
+            .map(|(id, _)| id)
+    }
+
     fn find(&self, mut predicate: impl FnMut(&L::Node) -> bool) -> Option<&L::Node> {
         // We take an extra closure because otherwise we would have a && in the signature
         self.ancestors().find(|&node| predicate(node))
     }
 
-    fn contains(&self, node: &L::Node) -> bool
-    where
-        L::Node: Eq,
-    {
-        self.ancestors()
-            .find(|&ancestor| ancestor == node)
-            .is_some()
+    fn contains(&self, node: &L::Node) -> bool {
+        self.position(node).is_some()
     }
 
     fn map<'t, L2: Logic, F: Fn(&L::Node) -> &L2::Node>(
@@ -444,38 +857,96 @@ pub trait Branch<L: Logic> {
     }
 }
 
+/// A branch of either a [`PartialTableau`] or a [`Tableau`].
 struct SimpleBranch<'t, L: Logic> {
     leaf: NodeId,
-    tableau: &'t PartialTableau<L>,
+    logic: &'t L,
+    nodes: &'t [TableauNode<L::Node>],
+    index: &'t BranchIndex,
+}
+
+impl<'t, L: Logic> SimpleBranch<'t, L> {
+    /// Nodes in this branch indexed under `key` whose place is `place`, which
+    /// leaves out other nodes that happen to have the same hash.
+    fn with_key<'a>(
+        &'a self,
+        key: WorldKey,
+        place: impl Fn(Place) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a L::Node> {
+        self.with_hash(BranchIndex::hash(&key))
+            .map(|(_, node)| node)
+            .filter(move |node| self.logic.place(node).is_some_and(&place))
+    }
 }
 
 impl<'t, L: Logic> Branch<L> for SimpleBranch<'t, L> {
     fn leaf(&self) -> &<L as Logic>::Node {
-        &self.tableau.get(self.leaf).value
+        &self.nodes[self.leaf.index as usize].value
     }
 
-    fn ancestors<'a>(&'a self) -> impl Iterator<Item = &'a L::Node>
+    fn ancestors_with_id<'a>(&'a self) -> impl Iterator<Item = (NodeId, &'a L::Node)>
     where
         L::Node: 'a,
     {
-        std::iter::once(self.leaf()).chain(AncestorIter {
-            tableau: self.tableau,
+        std::iter::once((self.leaf, self.leaf())).chain(AncestorIter {
+            nodes: self.nodes,
             current: self.leaf,
         })
     }
+
+    fn with_hash<'a>(&'a self, hash: u64) -> impl Iterator<Item = (NodeId, &'a L::Node)>
+    where
+        L::Node: 'a,
+    {
+        let trie = self.nodes[self.leaf.index as usize].trie;
+        self.index
+            .get(trie, hash)
+            .map(|id| (id, &self.nodes[id.index as usize].value))
+    }
+
+    fn worlds(&self) -> usize {
+        self.nodes[self.leaf.index as usize].worlds
+    }
+
+    fn at_world<'a>(&'a self, world: World) -> impl Iterator<Item = &'a L::Node>
+    where
+        L::Node: 'a,
+    {
+        self.with_key(WorldKey::At(world), move |place| place == Place::At(world))
+    }
+
+    fn relations_from<'a>(&'a self, world: World) -> impl Iterator<Item = &'a L::Node>
+    where
+        L::Node: 'a,
+    {
+        self.with_key(
+            WorldKey::From(world),
+            move |place| matches!(place, Place::Relation { from, .. } if from == world),
+        )
+    }
+
+    fn relations_to<'a>(&'a self, world: World) -> impl Iterator<Item = &'a L::Node>
+    where
+        L::Node: 'a,
+    {
+        self.with_key(
+            WorldKey::To(world),
+            move |place| matches!(place, Place::Relation { to, .. } if to == world),
+        )
+    }
 }
 
-struct AncestorIter<'t, L: Logic> {
-    tableau: &'t PartialTableau<L>,
+struct AncestorIter<'t, V> {
+    nodes: &'t [TableauNode<V>],
     current: NodeId,
 }
 
-impl<'t, L: Logic> Iterator for AncestorIter<'t, L> {
-    type Item = &'t L::Node;
+impl<'t, V> Iterator for AncestorIter<'t, V> {
+    type Item = (NodeId, &'t V);
     fn next(&mut self) -> Option<Self::Item> {
-        let parent = self.tableau.get(self.current).parent?;
+        let parent = self.nodes[self.current.index as usize].parent?;
         self.current = parent;
-        Some(&self.tableau.get(self.current).value)
+        Some((self.current, &self.nodes[self.current.index as usize].value))
     }
 }
 
@@ -489,14 +960,46 @@ impl<'t, B: Branch<L1>, L1: Logic, L2: Logic, F: Fn(&L1::Node) -> &L2::Node> Bra
     for MappedBranch<'t, B, L1, L2, F>
 {
     fn leaf(&self) -> &L2::Node {
-        (self.map_fn)(&self.branch.leaf())
+        (self.map_fn)(self.branch.leaf())
+    }
+
+    fn ancestors_with_id<'a>(&'a self) -> impl Iterator<Item = (NodeId, &'a L2::Node)>
+    where
+        L2::Node: 'a,
+    {
+        (self.branch.ancestors_with_id()).map(|(id, node)| (id, (self.map_fn)(node)))
+    }
+
+    fn with_hash<'a>(&'a self, hash: u64) -> impl Iterator<Item = (NodeId, &'a L2::Node)>
+    where
+        L2::Node: 'a,
+    {
+        (self.branch.with_hash(hash)).map(|(id, node)| (id, (self.map_fn)(node)))
+    }
+
+    fn worlds(&self) -> usize {
+        self.branch.worlds()
+    }
+
+    fn at_world<'a>(&'a self, world: World) -> impl Iterator<Item = &'a L2::Node>
+    where
+        L2::Node: 'a,
+    {
+        (self.branch.at_world(world)).map(&self.map_fn)
     }
 
-    fn ancestors<'a>(&'a self) -> impl Iterator<Item = &'a L2::Node>
+    fn relations_from<'a>(&'a self, world: World) -> impl Iterator<Item = &'a L2::Node>
     where
         L2::Node: 'a,
     {
-        (self.branch.ancestors()).map(|node| (self.map_fn)(node))
+        (self.branch.relations_from(world)).map(&self.map_fn)
+    }
+
+    fn relations_to<'a>(&'a self, world: World) -> impl Iterator<Item = &'a L2::Node>
+    where
+        L2::Node: 'a,
+    {
+        (self.branch.relations_to(world)).map(&self.map_fn)
     }
 }
 
@@ -513,40 +1016,162 @@ where
     /// Parses an argument of the style of `Σ ⊢ A`, where `Σ` can be multiple expressions separated
     /// by commas.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let Some((premises, conclusion)) = s.split_once('⊢') else {
+        Self::parse_with(L::default(), s, L::Expr::from_str)
+    }
+}
+
+#[cfg(feature = "parse")]
+impl<L: Logic> PartialTableau<L> {
+    /// Parses an argument of the style of `Σ ⊢ A` (or `Σ |- A`), where `Σ`
+    /// can be multiple expressions separated by commas, and each expression
+    /// is parsed with `parse_expr`.
+    ///
+    /// Useful when the logic is only known at runtime, see [`FromStr`]
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::{PartialTableau, logic::DynLogic};
+    ///
+    /// let logic: DynLogic = "S4".parse().unwrap();
+    /// let tableau = PartialTableau::parse_with(logic.clone(), "□p |- □□p", |expr| {
+    ///     logic.parse_expr(expr)
+    /// });
+    /// assert!(tableau.unwrap().infer().holds());
+    /// ```
+    pub fn parse_with<E>(
+        logic: L,
+        s: &str,
+        mut parse_expr: impl FnMut(&str) -> Result<L::Expr, E>,
+    ) -> Result<Self, TableauParseError<E>> {
+        let Some((premises, conclusion)) = s.split_once('⊢').or_else(|| s.split_once("|-"))
+        else {
             return Err(TableauParseError::MissingInferenceSymbol);
         };
 
-        let premises = if premises.is_empty() {
+        // Where `part` starts in `s`, since it's a subslice of it.
+        let offset_of = |part: &str| part.as_ptr() as usize - s.as_ptr() as usize;
+        let mut parse_part = |part: &str, location| {
+            let part = part.trim();
+            parse_expr(part).map_err(|error| TableauParseError::ExpressionError {
+                location,
+                offset: offset_of(part),
+                error,
+            })
+        };
+
+        let premises = if premises.trim().is_empty() {
             Vec::new()
         } else {
             premises
                 .split(',')
-                .map(|premise| {
-                    L::Expr::from_str(premise.trim())
-                        .map_err(|inner_err| TableauParseError::ExpressionError(inner_err))
-                })
+                .enumerate()
+                .map(|(i, premise)| parse_part(premise, ArgumentPart::Premise(i)))
                 .collect::<Result<_, _>>()?
         };
 
-        let conclusion = L::Expr::from_str(conclusion)?;
+        if conclusion.trim().is_empty() {
+            return Err(TableauParseError::MissingConclusion);
+        }
+
+        let conclusion = parse_part(conclusion, ArgumentPart::Conclusion)?;
 
-        Ok(PartialTableau::new(L::default(), premises, conclusion))
+        Ok(PartialTableau::new(logic, premises, conclusion))
     }
 }
 
+/// Why an argument of the form `Σ ⊢ A` couldn't be parsed.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::{
+///     ArgumentPart, PartialTableau, TableauParseError,
+///     logic::classical::Classical,
+/// };
+///
+/// let err = "p, q ∧ ⊢ p".parse::<PartialTableau<Classical>>().unwrap_err();
+/// let TableauParseError::ExpressionError { location, error, .. } = &err else {
+///     panic!("expected an expression error");
+/// };
+///
+/// assert_eq!(*location, ArgumentPart::Premise(1));
+/// assert_eq!(error.expected, ["expression"]);
+/// assert_eq!(error.found, None);
+/// // Right after the `∧`, relative to the whole argument.
+/// assert_eq!(err.span(), Some(8..8));
+///
+/// assert!(matches!(
+///     "p ⊢ ".parse::<PartialTableau<Classical>>(),
+///     Err(TableauParseError::MissingConclusion),
+/// ));
+/// ```
 #[cfg(feature = "parse")]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TableauParseError<E> {
-    ExpressionError(E),
+    /// One of the expressions is invalid.
+    ExpressionError {
+        /// Which expression it is.
+        location: ArgumentPart,
+        /// Byte offset of the expression in the argument.
+        offset: usize,
+        error: E,
+    },
+    /// There is no `⊢` (or `|-`).
     MissingInferenceSymbol,
+    /// There is nothing after the `⊢`.
     MissingConclusion,
 }
 
+/// A premise (with its index, starting from 0) or the conclusion of an
+/// argument.
+#[cfg(feature = "parse")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArgumentPart {
+    Premise(usize),
+    Conclusion,
+}
+
+#[cfg(feature = "parse")]
+impl TableauParseError<crate::logic::ParseError> {
+    /// Byte range of the error in the whole argument, if it's in an
+    /// expression.
+    pub fn span(&self) -> Option<std::ops::Range<usize>> {
+        match self {
+            Self::ExpressionError { offset, error, .. } => {
+                Some(error.span.start + offset..error.span.end + offset)
+            }
+            Self::MissingInferenceSymbol | Self::MissingConclusion => None,
+        }
+    }
+}
+
+#[cfg(feature = "parse")]
+impl<E: fmt::Display> fmt::Display for TableauParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExpressionError {
+                location, error, ..
+            } => write!(f, "{location}: {error}"),
+            Self::MissingInferenceSymbol => {
+                f.write_str("missing `⊢` between premises and conclusion")
+            }
+            Self::MissingConclusion => f.write_str("missing conclusion after `⊢`"),
+        }
+    }
+}
+
+#[cfg(feature = "parse")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for TableauParseError<E> {}
+
 #[cfg(feature = "parse")]
-impl<E> From<E> for TableauParseError<E> {
-    fn from(err: E) -> Self {
-        Self::ExpressionError(err)
+impl fmt::Display for ArgumentPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Premise(i) => write!(f, "in premise {}", i + 1),
+            Self::Conclusion => f.write_str("in conclusion"),
+        }
     }
 }
 
@@ -555,22 +1180,29 @@ where
     L::Node: fmt::Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut queue = vec![(self.get(self.root), 0)];
+        let mut queue = vec![(self.root, 0)];
 
-        while let Some((node, depth)) = queue.pop() {
+        while let Some((node_id, depth)) = queue.pop() {
+            let node = self.get(node_id);
             for _ in 0..depth {
                 write!(f, "  ")?;
             }
 
-            write!(f, "{}", node.value)?;
+            write!(f, "{node_id}. {}", node.value)?;
+            if let Some(justification) = &node.justification {
+                write!(f, " [{justification}]")?;
+            }
             if node.is_dead() {
                 write!(f, " ✘")?;
             }
+            if let Some(DeathReason::Contradiction { ancestor }) = node.death_reason {
+                write!(f, " (contradicts {ancestor})")?;
+            }
             write!(f, " (live_children={})", node.live_children)?;
             writeln!(f)?;
 
             for &child in node.children.iter().rev() {
-                queue.push((self.get(child), depth + 1))
+                queue.push((child, depth + 1))
             }
         }
 
@@ -583,9 +1215,10 @@ where
     L::Node: fmt::Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut queue = vec![(self.get(self.root), 0)];
+        let mut queue = vec![(self.root, 0)];
 
-        while let Some((node, depth)) = queue.pop() {
+        while let Some((node_id, depth)) = queue.pop() {
+            let node = self.get(node_id);
             for _ in 0..depth {
                 write!(f, "  ")?;
             }
@@ -596,10 +1229,17 @@ where
                 anstyle::Style::new().bold()
             };
 
-            writeln!(f, "{style}{}{style:#}", node.value)?;
+            write!(f, "{node_id}. {style}{}{style:#}", node.value)?;
+            if let Some(justification) = &node.justification {
+                write!(f, " [{justification}]")?;
+            }
+            if let Some(DeathReason::Contradiction { ancestor }) = node.death_reason {
+                write!(f, " ✘ (contradicts {ancestor})")?;
+            }
+            writeln!(f)?;
 
             for &child in node.children.iter().rev() {
-                queue.push((self.get(child), depth + 1))
+                queue.push((child, depth + 1))
             }
         }
 
@@ -607,14 +1247,50 @@ where
     }
 }
 
+impl NodeId {
+    /// The id of the node at position `index` of the tableau.
+    ///
+    /// # Panics
+    ///
+    /// If `index` doesn't fit in a `u32`, since the ids would otherwise wrap
+    /// around and corrupt the tree.
+    fn from_index(index: usize) -> Self {
+        let index = u32::try_from(index)
+            .unwrap_or_else(|_| panic!("tableau can't have more than {} nodes", u32::MAX));
+        NodeId { index }
+    }
+}
+
+impl From<NodeId> for u32 {
+    fn from(node_id: NodeId) -> Self {
+        node_id.index
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.index)
+    }
+}
+
+impl fmt::Display for Justification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "from {} by {}", self.source, self.name)
+    }
+}
+
 impl cmp::Ord for NodeIdPriority {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.priority.cmp(&other.priority)
+        // Among nodes of the same priority, the oldest gets expanded first so
+        // that every node eventually gets its turn.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.node_id.index.cmp(&self.node_id.index))
     }
 }
 
 impl cmp::PartialOrd for NodeIdPriority {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        self.priority.partial_cmp(&other.priority)
+        Some(self.cmp(other))
     }
 }
@@ -0,0 +1,126 @@
+//! [Graphviz](https://graphviz.org) export of tableaux, for trees too big to
+//! follow in the indented [`Display`](std::fmt::Display) output.
+
+use std::fmt::{self, Write as _};
+
+use crate::{
+    Logic,
+    logic::{Print, Printer},
+};
+
+use super::{DeathReason, NodeId, PartialTableau, Tableau, TableauNode};
+
+impl<L: Logic> PartialTableau<L>
+where
+    L::Node: Print,
+{
+    /// The tableau as a Graphviz `digraph`, with an edge from each node to its
+    /// children and the justification of each node under it.
+    ///
+    /// Closed nodes are red, with a dashed edge from each contradiction to the
+    /// node it contradicts. Since the tableau may not be finished, leaves that
+    /// aren't closed are drawn like any other node, see [`Tableau::to_dot`].
+    pub fn to_dot(&self) -> String {
+        to_dot(&self.nodes, self.root, false)
+    }
+}
+
+impl<L: Logic> Tableau<L>
+where
+    L::Node: Print,
+{
+    /// Like [`PartialTableau::to_dot`], but open branches end in a bold green
+    /// node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::logic::classical;
+    ///
+    /// let tableau = classical::infer("p ∨ q ⊢ p");
+    /// assert_eq!(
+    ///     tableau.to_dot(),
+    ///     r#"digraph tableau {
+    ///   node [shape=box, fontname="monospace"];
+    ///   n0 [label="0. p ∨ q"];
+    ///   n0 -> n1;
+    ///   n1 [label="1. ¬p"];
+    ///   n1 -> n2;
+    ///   n1 -> n3;
+    ///   n2 [label="2. p\nfrom 0 by ∨", color=red, fontcolor=red];
+    ///   n2 -> n1 [style=dashed, color=red, constraint=false];
+    ///   n3 [label="3. q\nfrom 0 by ∨", color=darkgreen, penwidth=2];
+    /// }
+    /// "#,
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        to_dot(&self.nodes, self.root, true)
+    }
+}
+
+/// Writes the tree at `root`, marking live leaves as open if `finished`.
+fn to_dot<V: Print>(nodes: &[TableauNode<V>], root: NodeId, finished: bool) -> String {
+    let mut output = String::new();
+    write_dot(&mut output, nodes, root, finished).expect("writing to a string can't fail");
+    output
+}
+
+fn write_dot<V: Print>(
+    f: &mut String,
+    nodes: &[TableauNode<V>],
+    root: NodeId,
+    finished: bool,
+) -> fmt::Result {
+    writeln!(f, "digraph tableau {{")?;
+    writeln!(f, "  node [shape=box, fontname=\"monospace\"];")?;
+
+    let mut queue = vec![root];
+    while let Some(node_id) = queue.pop() {
+        let node = &nodes[node_id.index as usize];
+
+        let mut label = format!("{node_id}. {}", Printer::new().print(&node.value));
+        if let Some(justification) = &node.justification {
+            write!(label, "\n{justification}")?;
+        }
+
+        write!(f, "  n{node_id} [label=\"{}\"", escape(&label))?;
+        if node.is_dead() {
+            f.write_str(", color=red, fontcolor=red")?;
+        } else if finished && node.children.is_empty() {
+            f.write_str(", color=darkgreen, penwidth=2")?;
+        }
+        writeln!(f, "];")?;
+
+        if let Some(DeathReason::Contradiction { ancestor }) = node.death_reason {
+            writeln!(
+                f,
+                "  n{node_id} -> n{ancestor} [style=dashed, color=red, constraint=false];"
+            )?;
+        }
+
+        for child in &node.children {
+            writeln!(f, "  n{node_id} -> n{child};")?;
+        }
+
+        queue.extend(node.children.iter().rev());
+    }
+
+    writeln!(f, "}}")
+}
+
+/// Escapes `s` to go inside a quoted DOT string, where a newline starts a new
+/// line of the label.
+pub(crate) fn escape(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    for char in s.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            char => output.push(char),
+        }
+    }
+
+    output
+}
@@ -0,0 +1,137 @@
+//! Index of the nodes of every branch of a tableau, to look up nodes in a
+//! branch without walking all of its ancestors.
+//!
+//! Each branch has its own persistent hash trie, and a child's trie is its
+//! parent's with one node inserted. Insertion copies only the path to the new
+//! node, so sibling branches share everything above the split and adding a
+//! node costs `O(log n)` both in time and memory.
+
+use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher, Hash};
+
+use crate::logic::modal::World;
+
+use super::NodeId;
+
+/// Bits of the hash consumed at each level of the trie.
+const BITS: u32 = 4;
+const WIDTH: usize = 1 << BITS;
+
+/// Reference to an entry of a [`BranchIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Trie(u32);
+
+impl Trie {
+    /// The trie without any nodes.
+    pub(crate) const EMPTY: Self = Trie(u32::MAX);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    Inner([Trie; WIDTH]),
+    /// A node with the given hash, followed by the rest of the nodes with the
+    /// same hash.
+    Leaf {
+        hash: u64,
+        node_id: NodeId,
+        next: Trie,
+    },
+}
+
+/// Keys under which nodes are indexed besides their own hash, so that they
+/// can also be looked up by world, see [`Logic::place`](crate::Logic::place).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WorldKey {
+    /// Nodes at the world.
+    At(World),
+    /// Relations from the world.
+    From(World),
+    /// Relations to the world.
+    To(World),
+}
+
+/// Arena holding the tries of every branch of a tableau.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BranchIndex {
+    entries: Vec<Entry>,
+}
+
+impl BranchIndex {
+    /// The hash used to index `value`. It is deterministic, so that it can be
+    /// recomputed by anyone looking up a node.
+    pub(crate) fn hash(value: &impl Hash) -> u64 {
+        BuildHasherDefault::<DefaultHasher>::default().hash_one(value)
+    }
+
+    /// Returns a new trie with every node of `trie` plus `node_id`. The
+    /// original trie is left intact.
+    pub(crate) fn insert(&mut self, trie: Trie, hash: u64, node_id: NodeId) -> Trie {
+        self.insert_at(trie, hash, node_id, 0)
+    }
+
+    fn insert_at(&mut self, trie: Trie, hash: u64, node_id: NodeId, shift: u32) -> Trie {
+        let Some(entry) = self.entries.get(trie.0 as usize) else {
+            return self.push(Entry::Leaf {
+                hash,
+                node_id,
+                next: Trie::EMPTY,
+            });
+        };
+
+        let mut children = match entry {
+            Entry::Inner(children) => *children,
+            Entry::Leaf { hash: other, .. } if *other == hash => {
+                return self.push(Entry::Leaf {
+                    hash,
+                    node_id,
+                    next: trie,
+                });
+            }
+            // Different hashes always differ in some later slot, so pushing
+            // the leaf one level down eventually separates them.
+            Entry::Leaf { hash: other, .. } => {
+                let mut children = [Trie::EMPTY; WIDTH];
+                children[slot(*other, shift)] = trie;
+                children
+            }
+        };
+
+        let slot = slot(hash, shift);
+        children[slot] = self.insert_at(children[slot], hash, node_id, shift + BITS);
+        self.push(Entry::Inner(children))
+    }
+
+    /// Iterator over the nodes in `trie` with the given hash, most recently
+    /// inserted first.
+    pub(crate) fn get(&self, trie: Trie, hash: u64) -> impl Iterator<Item = NodeId> + '_ {
+        let mut current = trie;
+        let mut shift = 0;
+        loop {
+            match self.entries.get(current.0 as usize) {
+                Some(Entry::Inner(children)) => {
+                    current = children[slot(hash, shift)];
+                    shift += BITS;
+                }
+                Some(Entry::Leaf { hash: other, .. }) if *other != hash => current = Trie::EMPTY,
+                _ => break,
+            }
+        }
+
+        std::iter::from_fn(move || match self.entries.get(current.0 as usize)? {
+            Entry::Leaf { node_id, next, .. } => {
+                current = *next;
+                Some(*node_id)
+            }
+            Entry::Inner(_) => unreachable!("leaves only point to other leaves"),
+        })
+    }
+
+    fn push(&mut self, entry: Entry) -> Trie {
+        let trie = Trie(u32::try_from(self.entries.len()).expect("branch index is full"));
+        self.entries.push(entry);
+        trie
+    }
+}
+
+fn slot(hash: u64, shift: u32) -> usize {
+    (hash >> shift) as usize % WIDTH
+}
@@ -0,0 +1,132 @@
+//! LaTeX export of tableaux, drawn like in Graham Priest's *An Introduction to
+//! Non-Classical Logic*: nodes of the same branch are stacked without lines,
+//! every closed branch ends in `×` and every open one in `↓`.
+
+use std::fmt::{self, Write as _};
+
+use crate::{
+    Logic,
+    logic::{Notation, Print, Printer},
+};
+
+use super::{NodeId, Tableau};
+
+/// The LaTeX package that [`Tableau::to_latex`] writes for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LatexPackage {
+    /// A `forest` environment from the
+    /// [forest](https://ctan.org/pkg/forest) package.
+    #[default]
+    Forest,
+    /// A `prooftree` environment from the
+    /// [prooftrees](https://ctan.org/pkg/prooftrees) package.
+    Prooftrees,
+}
+
+impl<L: Logic> Tableau<L>
+where
+    L::Node: Print,
+{
+    /// LaTeX source that draws the tableau with the given package, to be
+    /// included in a document that loads it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::{LatexPackage, logic::modal};
+    ///
+    /// let tableau = modal::infer("⊢ □p ⊃ p");
+    /// assert_eq!(
+    ///     tableau.to_latex(LatexPackage::Forest),
+    ///     r"\begin{forest}
+    ///   for tree={math content},
+    ///   where n children=1{for children={no edge}}{},
+    ///   [{\neg (\Box p \supset p), 0}
+    ///     [{\Box p, 0}
+    ///       [{\neg p, 0}
+    ///         [\downarrow]]]]
+    /// \end{forest}
+    /// ",
+    /// );
+    ///
+    /// let tableau = modal::infer("□p, ◇q ⊢ ◇p");
+    /// assert_eq!(
+    ///     tableau.to_latex(LatexPackage::Prooftrees),
+    ///     r"\begin{prooftree}{line numbering=false, open with={\ensuremath{\downarrow}}}
+    ///   [{\Box p, 0}
+    ///     [{\Diamond q, 0}
+    ///       [{\neg \Diamond p, 0}
+    ///         [{0r1}
+    ///           [{q, 1}
+    ///             [{\Box \neg p, 0}
+    ///               [{p, 1}
+    ///                 [{\neg p, 1}, close]]]]]]]]
+    /// \end{prooftree}
+    /// ",
+    /// );
+    /// ```
+    pub fn to_latex(&self, package: LatexPackage) -> String {
+        let mut output = String::new();
+        self.write_latex(&mut output, package)
+            .expect("writing to a string can't fail");
+        output
+    }
+
+    fn write_latex(&self, f: &mut String, package: LatexPackage) -> fmt::Result {
+        match package {
+            LatexPackage::Forest => f.write_str(concat!(
+                "\\begin{forest}\n",
+                "  for tree={math content},\n",
+                // Lines only join a node to its children where the branch
+                // splits.
+                "  where n children=1{for children={no edge}}{},\n",
+            ))?,
+            LatexPackage::Prooftrees => f.write_str(concat!(
+                "\\begin{prooftree}",
+                "{line numbering=false, open with={\\ensuremath{\\downarrow}}}\n",
+            ))?,
+        }
+
+        self.write_latex_node(f, package, self.root, 1)?;
+        writeln!(f)?;
+
+        match package {
+            LatexPackage::Forest => f.write_str("\\end{forest}\n"),
+            LatexPackage::Prooftrees => f.write_str("\\end{prooftree}\n"),
+        }
+    }
+
+    /// Writes the node and everything below it, indented `depth` levels.
+    fn write_latex_node(
+        &self,
+        f: &mut String,
+        package: LatexPackage,
+        node_id: NodeId,
+        depth: usize,
+    ) -> fmt::Result {
+        let node = self.get(node_id);
+        let indent = "  ".repeat(depth);
+
+        write!(f, "{indent}[{{")?;
+        Printer::new()
+            .notation(Notation::Latex)
+            .write(f, &node.value)?;
+        f.write_char('}')?;
+
+        if node.children.is_empty() {
+            match package {
+                LatexPackage::Forest if node.is_dead() => write!(f, "\n{indent}  [\\times]")?,
+                LatexPackage::Forest => write!(f, "\n{indent}  [\\downarrow]")?,
+                LatexPackage::Prooftrees if node.is_dead() => f.write_str(", close")?,
+                LatexPackage::Prooftrees => f.write_str(", open")?,
+            }
+        }
+
+        for &child in &node.children {
+            writeln!(f)?;
+            self.write_latex_node(f, package, child, depth + 1)?;
+        }
+
+        f.write_char(']')
+    }
+}
@@ -0,0 +1,253 @@
+//! Budgeted inference, for inputs whose tableaux might not terminate (or take
+//! too long to).
+
+use std::{fmt, time::Instant};
+
+use crate::{Logic, logic::InferenceRule};
+
+use super::{Branch, NodeId, PartialTableau, live_leaves};
+
+/// Bounds on how much work [`PartialTableau::infer_with_limits`] may do.
+///
+/// Every bound is optional, and [`Limits::new`] starts without any.
+///
+/// Limits are checked between inference steps, and a single step can add
+/// several nodes (or a fresh world), so the tableau can end up slightly
+/// over `max_nodes`, `max_depth` or `max_worlds`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::{Duration, Instant};
+/// use tableaux::Limits;
+///
+/// let limits = Limits::new()
+///     .max_nodes(10_000)
+///     .max_worlds(32)
+///     .deadline(Instant::now() + Duration::from_secs(1));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum number of nodes in the tableau.
+    pub max_nodes: Option<usize>,
+    /// Maximum depth of a node, where the root has depth 0.
+    pub max_depth: Option<usize>,
+    /// Maximum number of worlds, see [`Logic::worlds_in`].
+    pub max_worlds: Option<usize>,
+    /// Maximum number of inference steps in a single call.
+    pub max_steps: Option<u32>,
+    /// Point in time after which no more steps are carried out.
+    ///
+    /// Note that [`Instant::now`] is not available on `wasm32-unknown-unknown`,
+    /// so this should be left unset there.
+    pub deadline: Option<Instant>,
+}
+
+impl Limits {
+    /// No limits at all.
+    pub const fn new() -> Self {
+        Self {
+            max_nodes: None,
+            max_depth: None,
+            max_worlds: None,
+            max_steps: None,
+            deadline: None,
+        }
+    }
+
+    pub const fn max_nodes(self, max_nodes: usize) -> Self {
+        Self {
+            max_nodes: Some(max_nodes),
+            ..self
+        }
+    }
+
+    pub const fn max_depth(self, max_depth: usize) -> Self {
+        Self {
+            max_depth: Some(max_depth),
+            ..self
+        }
+    }
+
+    pub const fn max_worlds(self, max_worlds: usize) -> Self {
+        Self {
+            max_worlds: Some(max_worlds),
+            ..self
+        }
+    }
+
+    pub const fn max_steps(self, max_steps: u32) -> Self {
+        Self {
+            max_steps: Some(max_steps),
+            ..self
+        }
+    }
+
+    pub const fn deadline(self, deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+}
+
+/// One of the bounds in [`Limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    Nodes,
+    Depth,
+    Worlds,
+    Steps,
+    Time,
+}
+
+/// How a call to [`PartialTableau::infer_with_limits`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InferenceStatus {
+    /// Every branch is closed, so the argument holds.
+    Closed,
+    /// Nothing more can be inferred and some branch is open, so the argument
+    /// doesn't hold.
+    Open,
+    /// The given limit was reached before the tableau was finished.
+    OutOfBudget(Limit),
+}
+
+impl<L: Logic> PartialTableau<L>
+where
+    L::Node: Eq,
+{
+    /// Like [`Self::infer`], but stops once any of the `limits` is reached
+    /// while there is still something to infer.
+    ///
+    /// The tableau is left in `self`, so it can be inspected or inference
+    /// resumed (possibly with other limits). Once it's finished,
+    /// [`Self::infer`] gives the completed [`Tableau`](crate::Tableau)
+    /// without doing any more work.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tableaux::{
+    ///     InferenceStatus, Limit, Limits, PartialTableau,
+    ///     logic::{Logic, classical::Classical, normal_modal},
+    /// };
+    ///
+    /// let mut tableau = normal_modal::D.tableau(["p".parse().unwrap()], "q".parse().unwrap());
+    /// assert_eq!(
+    ///     tableau.infer_with_limits(&Limits::new().max_steps(1_000)),
+    ///     InferenceStatus::Open,
+    /// );
+    ///
+    /// let mut tableau = normal_modal::S4.tableau([], "□p ⊃ p".parse().unwrap());
+    /// let status = tableau.infer_with_limits(&Limits::new().max_steps(1));
+    /// assert_eq!(status, InferenceStatus::OutOfBudget(Limit::Steps));
+    /// assert_eq!(tableau.infer_with_limits(&Limits::new()), InferenceStatus::Closed);
+    ///
+    /// // A tableau with nothing left to add isn't out of budget.
+    /// let mut tableau = "p ⊢ q".parse::<PartialTableau<Classical>>().unwrap();
+    /// assert_eq!(
+    ///     tableau.infer_with_limits(&Limits::new().max_nodes(2).max_steps(0)),
+    ///     InferenceStatus::Open,
+    /// );
+    /// ```
+    pub fn infer_with_limits(&mut self, limits: &Limits) -> InferenceStatus {
+        let mut steps = 0;
+        loop {
+            if self.get(self.root).is_dead() {
+                return InferenceStatus::Closed;
+            }
+
+            if let Some(limit) = self.exceeded_limit(limits, steps) {
+                return if self.is_finished() {
+                    InferenceStatus::Open
+                } else {
+                    InferenceStatus::OutOfBudget(limit)
+                };
+            }
+
+            if self.infer_once().is_none() {
+                return if self.get(self.root).is_dead() {
+                    InferenceStatus::Closed
+                } else {
+                    InferenceStatus::Open
+                };
+            }
+
+            steps += 1;
+        }
+    }
+
+    /// Whether going on with inference wouldn't add any node, even if some
+    /// nodes are still waiting to be inferred.
+    fn is_finished(&self) -> bool {
+        let adds_nothing = |leaf: NodeId, inference: InferenceRule<L::Node>| {
+            let branch = self.branch(leaf);
+            let contains_all = |nodes: &[L::Node]| nodes.iter().all(|node| branch.contains(node));
+            match inference {
+                InferenceRule::None => true,
+                InferenceRule::Single(node) => branch.contains(&node),
+                InferenceRule::Chain(nodes) => contains_all(&nodes),
+                InferenceRule::Split(nodes) => nodes.iter().any(|node| branch.contains(node)),
+                InferenceRule::SplitAndChain(chains) => {
+                    chains.iter().any(|chain| contains_all(chain))
+                }
+                InferenceRule::SplitChains(chains) => {
+                    chains.iter().any(|chain| contains_all(chain))
+                }
+            }
+        };
+
+        self.uninferred_nodes.iter().all(|priority| {
+            let node = &self.get(priority.node_id).value;
+            live_leaves(&self.nodes, priority.node_id)
+                .into_iter()
+                .all(|leaf| adds_nothing(leaf, self.logic.infer(node, self.branch(leaf))))
+        }) && self
+            .live_leaves()
+            .into_iter()
+            .all(|leaf| adds_nothing(leaf, self.logic.saturate(self.branch(leaf))))
+    }
+
+    /// The first limit that the tableau has reached, if any.
+    fn exceeded_limit(&self, limits: &Limits, steps: u32) -> Option<Limit> {
+        if limits.max_nodes.is_some_and(|max| self.nodes.len() >= max) {
+            Some(Limit::Nodes)
+        } else if limits.max_depth.is_some_and(|max| self.max_depth >= max) {
+            Some(Limit::Depth)
+        } else if limits.max_worlds.is_some_and(|max| self.worlds >= max) {
+            Some(Limit::Worlds)
+        } else if limits.max_steps.is_some_and(|max| steps >= max) {
+            Some(Limit::Steps)
+        } else if limits
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(Limit::Time)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Nodes => "node limit",
+            Self::Depth => "depth limit",
+            Self::Worlds => "world limit",
+            Self::Steps => "step limit",
+            Self::Time => "time limit",
+        })
+    }
+}
+
+impl fmt::Display for InferenceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => f.write_str("closed"),
+            Self::Open => f.write_str("open"),
+            Self::OutOfBudget(limit) => write!(f, "ran out of budget ({limit})"),
+        }
+    }
+}
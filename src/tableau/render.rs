@@ -0,0 +1,411 @@
+//! Drawing tableaux in the terminal with box-drawing characters, with the
+//! branches of a split side by side when they fit in the width of the screen.
+
+use std::fmt::Write as _;
+
+use anstyle::{AnsiColor, Style};
+
+use crate::{
+    Logic,
+    logic::{Notation, Print, Printer},
+};
+
+use super::{DeathReason, NodeId, PartialTableau, Tableau, TableauNode};
+
+/// Columns between branches drawn side by side, and in front of branches
+/// drawn one below the other.
+const INDENT: usize = 3;
+
+/// Draws tableaux for the terminal.
+///
+/// Every node is written as its number, its value and, if it was inferred,
+/// where it comes from. Closed branches end in `×` followed by the number of
+/// the node the leaf contradicts, and open branches of finished tableaux end
+/// in `↓`. The branches of a split are drawn side by side under a `┌─┴─┐`
+/// when they fit in [`Self::width`] columns, or one below the other after
+/// `├─` and `└─` when they don't.
+///
+/// # Example
+///
+/// ```rust
+/// use tableaux::{Renderer, logic::classical};
+///
+/// let tableau = classical::infer("p ∨ q, ¬q ⊢ p");
+/// assert_eq!(
+///     Renderer::new().render(&tableau),
+///     "               0. p ∨ q
+///                  1. ¬q
+///                  2. ¬p
+///          ┌─────────┴──────────┐
+/// 3. p [from 0 by ∨]   4. q [from 0 by ∨]
+///       × (2)                × (1)
+/// ",
+/// );
+///
+/// assert_eq!(
+///     Renderer::new().width(30).render(&tableau),
+///     "0. p ∨ q
+/// 1. ¬q
+/// 2. ¬p
+/// ├─ 3. p [from 0 by ∨]
+/// │        × (2)
+/// └─ 4. q [from 0 by ∨]
+///          × (1)
+/// ",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Renderer {
+    /// Maximum number of columns, assuming every character takes one.
+    pub width: usize,
+    /// Whether to colour the output with ANSI escape codes.
+    pub color: bool,
+    /// How to write the values of the nodes.
+    pub notation: Notation,
+}
+
+/// A piece of text starting at some column of a row.
+#[derive(Debug, Clone, Default)]
+struct Segment {
+    column: usize,
+    /// The text, including escape codes if colours are enabled.
+    text: String,
+    /// The number of columns the text takes.
+    width: usize,
+}
+
+/// The drawing of a subtree.
+#[derive(Debug, Clone, Default)]
+struct Block {
+    rows: Vec<Vec<Segment>>,
+    width: usize,
+    /// The column that the line from the parent points to.
+    anchor: usize,
+}
+
+struct Layout<'a, V> {
+    renderer: &'a Renderer,
+    nodes: &'a [TableauNode<V>],
+    /// Whether live leaves are open branches.
+    finished: bool,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    /// 80 columns without colours, in unicode notation.
+    pub const fn new() -> Self {
+        Self {
+            width: 80,
+            color: false,
+            notation: Notation::Unicode,
+        }
+    }
+
+    pub const fn width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    pub const fn color(self, color: bool) -> Self {
+        Self { color, ..self }
+    }
+
+    pub const fn notation(self, notation: Notation) -> Self {
+        Self { notation, ..self }
+    }
+
+    pub fn render<L: Logic>(&self, tableau: &Tableau<L>) -> String
+    where
+        L::Node: Print,
+    {
+        self.render_tree(&tableau.nodes, tableau.root, true)
+    }
+
+    /// Like [`Self::render`], but since the tableau may not be finished, live
+    /// leaves don't end in `↓`.
+    pub fn render_partial<L: Logic>(&self, tableau: &PartialTableau<L>) -> String
+    where
+        L::Node: Print,
+    {
+        self.render_tree(&tableau.nodes, tableau.root, false)
+    }
+
+    fn render_tree<V: Print>(
+        &self,
+        nodes: &[TableauNode<V>],
+        root: NodeId,
+        finished: bool,
+    ) -> String {
+        let layout = Layout {
+            renderer: self,
+            nodes,
+            finished,
+        };
+
+        let mut output = String::new();
+        for mut row in layout.block(root, self.width).rows {
+            row.sort_by_key(|segment| segment.column);
+
+            let mut column = 0;
+            for segment in row {
+                output.extend(std::iter::repeat_n(
+                    ' ',
+                    segment.column.saturating_sub(column),
+                ));
+                output.push_str(&segment.text);
+                column = segment.column + segment.width;
+            }
+
+            output.push('\n');
+        }
+
+        output
+    }
+
+    fn paint(&self, segment: &mut Segment, style: Style, text: &str) {
+        if self.color && style != Style::new() {
+            write!(segment.text, "{style}{text}{style:#}").expect("writing to a string can't fail");
+        } else {
+            segment.text.push_str(text);
+        }
+
+        segment.width += text.chars().count();
+    }
+}
+
+impl<V: Print> Layout<'_, V> {
+    fn get(&self, node_id: NodeId) -> &TableauNode<V> {
+        &self.nodes[node_id.index as usize]
+    }
+
+    /// Draws the subtree at `node_id` in `max_width` columns, if possible.
+    fn block(&self, node_id: NodeId, max_width: usize) -> Block {
+        // Nodes with a single child are just written one below the other.
+        let mut lines = Vec::new();
+        let mut node_id = node_id;
+        loop {
+            lines.push(self.label(node_id));
+            match self.get(node_id).children.as_slice() {
+                &[child] => node_id = child,
+                _ => break,
+            }
+        }
+
+        let node = self.get(node_id);
+        if node.children.is_empty() {
+            lines.extend(self.mark(node));
+            return centered(lines);
+        }
+
+        let children = node
+            .children
+            .iter()
+            .map(|&child| self.block(child, max_width.saturating_sub(INDENT)))
+            .collect::<Vec<_>>();
+
+        let side_by_side =
+            children.iter().map(|child| child.width).sum::<usize>() + INDENT * (children.len() - 1);
+        if side_by_side <= max_width {
+            self.side_by_side(lines, children)
+        } else {
+            self.one_below_the_other(lines, children)
+        }
+    }
+
+    /// Centers `lines` over `children`, joined to them with `┌─┴─┐`.
+    fn side_by_side(&self, lines: Vec<Segment>, children: Vec<Block>) -> Block {
+        let mut rows = Vec::<Vec<Segment>>::new();
+        let mut anchors = Vec::new();
+        let mut column = 0;
+        for mut child in children {
+            child.shift(column);
+            anchors.push(child.anchor);
+            column = child.width + INDENT;
+
+            if rows.len() < child.rows.len() {
+                rows.resize_with(child.rows.len(), Vec::new);
+            }
+            for (row, segments) in rows.iter_mut().zip(child.rows) {
+                row.extend(segments);
+            }
+        }
+
+        let (first, last) = (anchors[0], anchors[anchors.len() - 1]);
+        let center = (first + last) / 2;
+        let connector = (first..=last)
+            .map(|column| match column {
+                column if column == center && anchors.contains(&column) => '┼',
+                column if column == center => '┴',
+                column if column == first => '┌',
+                column if column == last => '┐',
+                column if anchors.contains(&column) => '┬',
+                _ => '─',
+            })
+            .collect::<String>();
+
+        // Lines wider than the branches under them push the branches right.
+        let shift = lines
+            .iter()
+            .map(|line| (line.width / 2).saturating_sub(center))
+            .max()
+            .unwrap_or(0);
+        let anchor = center + shift;
+
+        let mut connector_segment = Segment {
+            column: first + shift,
+            ..Default::default()
+        };
+        self.renderer
+            .paint(&mut connector_segment, Style::new().dimmed(), &connector);
+
+        let mut width = column - INDENT + shift;
+        let mut block_rows = Vec::with_capacity(lines.len() + 1 + rows.len());
+        for mut line in lines {
+            line.column = anchor - line.width / 2;
+            width = width.max(line.column + line.width);
+            block_rows.push(vec![line]);
+        }
+
+        block_rows.push(vec![connector_segment]);
+        block_rows.extend(rows.into_iter().map(|mut row| {
+            for segment in &mut row {
+                segment.column += shift;
+            }
+            row
+        }));
+
+        Block {
+            rows: block_rows,
+            width,
+            anchor,
+        }
+    }
+
+    /// Writes `lines` followed by each of `children` indented after a `├─`,
+    /// for splits too wide to draw side by side.
+    fn one_below_the_other(&self, lines: Vec<Segment>, children: Vec<Block>) -> Block {
+        let anchor = lines[0].width / 2;
+        let mut width = lines.iter().map(|line| line.width).max().unwrap_or(0);
+        let mut rows = lines.into_iter().map(|line| vec![line]).collect::<Vec<_>>();
+
+        let count = children.len();
+        for (i, mut child) in children.into_iter().enumerate() {
+            let last = i == count - 1;
+            child.shift(INDENT);
+            width = width.max(child.width);
+
+            for (j, mut row) in child.rows.into_iter().enumerate() {
+                let guide = if j == 0 {
+                    let start = row.iter().map(|segment| segment.column).min();
+                    let dashes = "─".repeat(start.unwrap_or(INDENT) - 2);
+                    Some(format!("{}{dashes} ", if last { '└' } else { '├' }))
+                } else if !last {
+                    Some("│".to_string())
+                } else {
+                    None
+                };
+
+                if let Some(guide) = guide {
+                    let mut segment = Segment::default();
+                    self.renderer
+                        .paint(&mut segment, Style::new().dimmed(), &guide);
+                    row.push(segment);
+                }
+
+                rows.push(row);
+            }
+        }
+
+        Block {
+            rows,
+            width,
+            anchor,
+        }
+    }
+
+    /// The number, value and justification of the node.
+    fn label(&self, node_id: NodeId) -> Segment {
+        let renderer = self.renderer;
+        let node = self.get(node_id);
+        let mut label = Segment::default();
+
+        renderer.paint(&mut label, Style::new().dimmed(), &format!("{node_id}."));
+        label.text.push(' ');
+        label.width += 1;
+
+        let value = Printer::new()
+            .notation(renderer.notation)
+            .print(&node.value);
+        let style = if node.is_dead() {
+            Style::new()
+        } else {
+            Style::new().bold()
+        };
+        renderer.paint(&mut label, style, &value);
+
+        if let Some(justification) = &node.justification {
+            label.text.push(' ');
+            label.width += 1;
+            renderer.paint(
+                &mut label,
+                Style::new().dimmed(),
+                &format!("[{justification}]"),
+            );
+        }
+
+        label
+    }
+
+    /// The end of the branch at the leaf, if it's closed or open.
+    fn mark(&self, leaf: &TableauNode<V>) -> Option<Segment> {
+        let renderer = self.renderer;
+        let mut mark = Segment::default();
+        match leaf.death_reason {
+            Some(death_reason) => {
+                renderer.paint(&mut mark, AnsiColor::Red.on_default().bold(), "×");
+                if let DeathReason::Contradiction { ancestor } = death_reason {
+                    renderer.paint(&mut mark, Style::new(), &format!(" ({ancestor})"));
+                }
+            }
+            None if self.finished => {
+                renderer.paint(&mut mark, AnsiColor::Green.on_default().bold(), "↓");
+            }
+            None => return None,
+        }
+
+        Some(mark)
+    }
+}
+
+impl Block {
+    fn shift(&mut self, columns: usize) {
+        for segment in self.rows.iter_mut().flatten() {
+            segment.column += columns;
+        }
+
+        self.width += columns;
+        self.anchor += columns;
+    }
+}
+
+/// A block of `lines` centered on each other.
+fn centered(lines: Vec<Segment>) -> Block {
+    let width = lines.iter().map(|line| line.width).max().unwrap_or(0);
+    let rows = lines
+        .into_iter()
+        .map(|mut line| {
+            line.column = (width - line.width) / 2;
+            vec![line]
+        })
+        .collect();
+
+    Block {
+        rows,
+        width,
+        anchor: width / 2,
+    }
+}
@@ -0,0 +1,333 @@
+//! Serialization of tableaux with [serde](https://serde.rs), so that they can
+//! be saved and loaded again, possibly to resume inference.
+//!
+//! Both [`PartialTableau`] and [`Tableau`] are written as the logic, the
+//! nodes in the order they were added (so the root is node 0 and every node
+//! comes after its parent), the nodes that are still to be inferred and the
+//! number of inference steps so far. Anything that can be recomputed from
+//! those, such as the index of each branch, is left out.
+
+use std::collections::BinaryHeap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+
+use crate::{InferenceStatus, Limits, Logic};
+
+use super::{
+    BranchIndex, DeathReason, Justification, NodeId, NodeIdPriority, PartialTableau, Tableau,
+    TableauNode, Trie,
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Tableau")]
+struct TableauData<L, N> {
+    logic: L,
+    nodes: Vec<NodeData<N>>,
+    /// Nodes that still have to be inferred, in no particular order.
+    uninferred: Vec<NodeId>,
+    steps: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Node")]
+struct NodeData<N> {
+    value: N,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    #[serde(default)]
+    death_reason: Option<DeathReason>,
+    #[serde(default)]
+    justification: Option<Justification>,
+}
+
+impl<'a, N> From<&'a TableauNode<N>> for NodeData<&'a N> {
+    fn from(node: &'a TableauNode<N>) -> Self {
+        NodeData {
+            value: &node.value,
+            parent: node.parent,
+            children: node.children.clone(),
+            death_reason: node.death_reason,
+            justification: node.justification.clone(),
+        }
+    }
+}
+
+impl<L: Logic> PartialTableau<L> {
+    /// Rebuilds the tableau, checking that the nodes do form a tree of the
+    /// logic.
+    fn from_data(data: TableauData<L, L::Node>) -> Result<Self, String> {
+        let len = data.nodes.len();
+        if len == 0 {
+            return Err("a tableau needs at least one node".to_string());
+        }
+
+        let check = |node_id: NodeId| {
+            if (node_id.index as usize) < len {
+                Ok(node_id)
+            } else {
+                Err(format!("node {node_id} doesn't exist"))
+            }
+        };
+
+        for (i, node) in data.nodes.iter().enumerate() {
+            let node_id = NodeId::from_index(i);
+            if !data.logic.owns_node(&node.value) {
+                return Err(format!("node {node_id} isn't of the tableau's logic"));
+            }
+
+            match node.parent {
+                None if i == 0 => {}
+                None => return Err(format!("node {node_id} has no parent")),
+                Some(parent) if parent.index as usize >= i => {
+                    return Err(format!("node {node_id} comes before its parent {parent}"));
+                }
+                Some(parent)
+                    if !data.nodes[parent.index as usize]
+                        .children
+                        .contains(&node_id) =>
+                {
+                    return Err(format!(
+                        "node {node_id} isn't a child of its parent {parent}"
+                    ));
+                }
+                Some(_) => {}
+            }
+
+            for &child in &node.children {
+                if data
+                    .nodes
+                    .get(child.index as usize)
+                    .map(|child| child.parent)
+                    != Some(Some(node_id))
+                {
+                    return Err(format!("node {child} isn't a child of node {node_id}"));
+                }
+            }
+
+            match node.death_reason {
+                Some(DeathReason::Contradiction { ancestor }) => {
+                    // Every parent was already checked to come before its
+                    // child, so this walks up to the root.
+                    let mut ancestors = std::iter::successors(node.parent, |parent| {
+                        data.nodes[parent.index as usize].parent
+                    });
+                    if !ancestors.any(|other| other == ancestor) {
+                        return Err(format!(
+                            "node {node_id} contradicts node {ancestor}, which isn't one of its ancestors"
+                        ));
+                    }
+                }
+                Some(DeathReason::ChildrenDied) => {
+                    let all_dead = node
+                        .children
+                        .iter()
+                        .all(|child| data.nodes[child.index as usize].death_reason.is_some());
+                    if node.children.is_empty() || !all_dead {
+                        return Err(format!(
+                            "node {node_id} died with its children, but not all of them are dead"
+                        ));
+                    }
+                }
+                None => {}
+            }
+
+            if let Some(justification) = &node.justification {
+                check(justification.source)?;
+            }
+        }
+
+        let mut tableau = PartialTableau {
+            logic: data.logic,
+            nodes: Vec::with_capacity(len),
+            root: NodeId { index: 0 },
+            index: BranchIndex::default(),
+            uninferred_nodes: BinaryHeap::new(),
+            steps: data.steps,
+            max_depth: 0,
+            worlds: 0,
+        };
+
+        for node in data.nodes {
+            let node_id = NodeId::from_index(tableau.nodes.len());
+            let depth = node
+                .parent
+                .map_or(0, |parent| tableau.get(parent).depth + 1);
+
+            tableau.max_depth = tableau.max_depth.max(depth as usize);
+            tableau.worlds = tableau.worlds.max(tableau.logic.worlds_in(&node.value));
+            tableau.nodes.push(TableauNode {
+                value: node.value,
+                parent: node.parent,
+                children: node.children,
+                live_children: 0,
+                death_reason: node.death_reason,
+                justification: node.justification,
+                depth,
+                trie: Trie::EMPTY,
+                worlds: 0,
+            });
+            tableau.index_node(node_id, node.parent);
+        }
+
+        // Children always come after their parents, so they are all known by
+        // now.
+        for i in 0..len {
+            let node_id = NodeId::from_index(i);
+            let live_children = tableau
+                .get(node_id)
+                .children
+                .iter()
+                .filter(|child| !tableau.get(**child).is_dead())
+                .count();
+            tableau.get_mut(node_id).live_children = u8::try_from(live_children)
+                .map_err(|_| format!("node {node_id} has too many children"))?;
+        }
+
+        for node_id in data.uninferred {
+            let priority = tableau.logic.priority(&tableau.get(check(node_id)?).value);
+            tableau
+                .uninferred_nodes
+                .push(NodeIdPriority { node_id, priority });
+        }
+
+        Ok(tableau)
+    }
+}
+
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "serde")] {
+/// use tableaux::{InferenceStatus, Limits, PartialTableau, logic::DynLogic};
+///
+/// let logic = "S4".parse::<DynLogic>().unwrap();
+/// let mut tableau = PartialTableau::parse_with(logic.clone(), "⊢ □p ⊃ □□p", |expr| {
+///     logic.parse_expr(expr)
+/// })
+/// .unwrap();
+///
+/// let status = tableau.infer_with_limits(&Limits::new().max_steps(3));
+/// assert!(matches!(status, InferenceStatus::OutOfBudget(_)));
+///
+/// // Save the tableau halfway and finish it after loading it again.
+/// let json = serde_json::to_string(&tableau).unwrap();
+/// let mut loaded = serde_json::from_str::<PartialTableau<DynLogic>>(&json).unwrap();
+/// assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+/// assert_eq!(loaded.infer_with_limits(&Limits::new()), InferenceStatus::Closed);
+///
+/// // The nodes have to belong to the logic.
+/// let mut value = serde_json::to_value(&tableau).unwrap();
+/// value["logic"] = serde_json::json!({ "Classical": {} });
+/// assert!(serde_json::from_value::<PartialTableau<DynLogic>>(value).is_err());
+/// # }
+/// ```
+impl<L> Serialize for PartialTableau<L>
+where
+    L: Logic + Serialize,
+    L::Node: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Sorted so that the same tableau is always written the same way.
+        let mut uninferred = self
+            .uninferred_nodes
+            .iter()
+            .map(|priority| priority.node_id)
+            .collect::<Vec<_>>();
+        uninferred.sort_by_key(|node_id| node_id.index);
+
+        TableauData {
+            logic: &self.logic,
+            nodes: self.nodes.iter().map(NodeData::from).collect(),
+            uninferred,
+            steps: self.steps,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, L> Deserialize<'de> for PartialTableau<L>
+where
+    L: Logic + Deserialize<'de>,
+    L::Node: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = TableauData::deserialize(deserializer)?;
+        Self::from_data(data).map_err(D::Error::custom)
+    }
+}
+
+/// Written the same as a [`PartialTableau`] without nodes left to infer.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "serde")] {
+/// use tableaux::{PartialTableau, Tableau, logic::classical::{self, Classical}};
+///
+/// let tableau = classical::infer("p ∨ q ⊢ p");
+/// let json = serde_json::to_value(&tableau).unwrap();
+/// assert_eq!(json["logic"], serde_json::json!({}));
+/// assert_eq!(json["nodes"][1]["value"], serde_json::json!({ "Not": { "Const": "p" } }));
+/// assert_eq!(json["nodes"][2]["death_reason"]["Contradiction"]["ancestor"], 1);
+///
+/// let loaded = serde_json::from_value::<Tableau<Classical>>(json.clone()).unwrap();
+/// assert_eq!(loaded.countermodels(), tableau.countermodels());
+///
+/// // Branches only close on their own nodes.
+/// let mut wrong = json.clone();
+/// wrong["nodes"][2]["death_reason"]["Contradiction"]["ancestor"] = serde_json::json!(2);
+/// assert!(serde_json::from_value::<Tableau<Classical>>(wrong).is_err());
+///
+/// // Unfinished tableaux have to be loaded as partial ones.
+/// let partial = "p ∧ q ⊢ p".parse::<PartialTableau<Classical>>().unwrap();
+/// let json = serde_json::to_value(&partial).unwrap();
+/// assert!(serde_json::from_value::<Tableau<Classical>>(json.clone()).is_err());
+/// assert!(serde_json::from_value::<PartialTableau<Classical>>(json).is_ok());
+/// # }
+/// ```
+impl<L> Serialize for Tableau<L>
+where
+    L: Logic + Serialize,
+    L::Node: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // The number of steps isn't kept once inference is over, but it's
+        // one more than the last step that added a node.
+        let steps = self
+            .nodes
+            .iter()
+            .filter_map(|node| Some(node.justification.as_ref()?.step + 1))
+            .max()
+            .unwrap_or(0);
+
+        TableauData {
+            logic: &self.logic,
+            nodes: self.nodes.iter().map(NodeData::from).collect(),
+            uninferred: Vec::new(),
+            steps,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Only finished tableaux can be loaded this way, since finishing one could
+/// take forever. Unfinished ones have to be loaded as a [`PartialTableau`]
+/// and inferred with [`Limits`](crate::Limits).
+impl<'de, L> Deserialize<'de> for Tableau<L>
+where
+    L: Logic + Deserialize<'de>,
+    L::Node: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut tableau = PartialTableau::deserialize(deserializer)?;
+
+        // Without any steps, inference only checks whether there's anything
+        // left to add.
+        match tableau.infer_with_limits(&Limits::new().max_steps(0)) {
+            InferenceStatus::OutOfBudget(_) => Err(D::Error::custom(
+                "the tableau isn't finished, load it as a partial tableau instead",
+            )),
+            InferenceStatus::Closed | InferenceStatus::Open => Ok(tableau.infer()),
+        }
+    }
+}